@@ -0,0 +1,133 @@
+use super::Box;
+use crate::buffer::AllocatedBuffer;
+use alloc::alloc::Global;
+use core::alloc::{AllocError, Allocator};
+#[cfg(feature = "global")]
+use {alloc::alloc::handle_alloc_error, core::alloc::Layout};
+
+/// A boxed slice of booleans, which stores eight booleans per byte.
+///
+/// As single bits can't be referenced, `BitBox` doesn't dereference to `[bool]` but provides
+/// [`get`] and [`set`] instead. The bits are stored in a boxed byte slice in an
+/// [`AllocatedBuffer`].
+///
+/// [`get`]: Self::get
+/// [`set`]: Self::set
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::BitBox;
+///
+/// let mut bits = BitBox::new(10);
+/// bits.set(3, true);
+/// bits.set(9, true);
+///
+/// assert!(bits.get(3));
+/// assert!(!bits.get(4));
+/// assert!(bits.get(9));
+/// # }
+/// ```
+pub struct BitBox<A: Allocator = Global> {
+    bytes: Box<[u8], AllocatedBuffer<[u8], A>, A>,
+    len: usize,
+}
+
+/// Returns the number of bytes required to store `len` bits.
+const fn byte_len(len: usize) -> usize {
+    len / 8 + (len % 8 != 0) as usize
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl BitBox {
+    /// Allocates `len` bits on the global heap, which are all `false`.
+    pub fn new(len: usize) -> Self {
+        Self::try_new_in(len, Global)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<u8>(byte_len(len)).unwrap()))
+    }
+}
+
+impl<A: Allocator> BitBox<A> {
+    /// Allocates `len` bits in the provided allocator, which are all `false`.
+    pub fn try_new_in(len: usize, allocator: A) -> Result<Self, AllocError> {
+        let buffer = AllocatedBuffer::new_slice_zeroed_exact(&allocator, byte_len(len))?;
+        let bytes = unsafe { Box::from_buffer(buffer, allocator) };
+        Ok(Self { bytes, len })
+    }
+
+    /// Returns the number of bits.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if there are no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bit at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "index out of bounds");
+        self.bytes[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    /// Sets the bit at `index` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "index out of bounds");
+        let mask = 1 << (index % 8);
+        if value {
+            self.bytes[index / 8] |= mask;
+        } else {
+            self.bytes[index / 8] &= !mask;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Counting;
+
+    #[test]
+    fn byte_boundaries() {
+        let counting = Counting::default();
+        let mut bits = BitBox::try_new_in(17, &counting).unwrap();
+        assert_eq!(bits.len(), 17);
+        assert_eq!(bits.bytes.len(), 3);
+
+        for &index in &[0, 7, 8, 15, 16] {
+            bits.set(index, true);
+        }
+        for index in 0..17 {
+            assert_eq!(bits.get(index), [0, 7, 8, 15, 16].contains(&index));
+        }
+
+        bits.set(7, false);
+        bits.set(8, false);
+        assert!(bits.get(0));
+        assert!(!bits.get(7));
+        assert!(!bits.get(8));
+        assert!(bits.get(15));
+
+        drop(bits);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn out_of_bounds() {
+        let bits = BitBox::new(8);
+        bits.get(8);
+    }
+}
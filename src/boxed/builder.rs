@@ -0,0 +1,171 @@
+use super::{handle_reserve, Box};
+use crate::buffer::AllocatedBuffer;
+use alloc::alloc::{handle_alloc_error, Global};
+use core::{
+    alloc::{Allocator, Layout},
+    mem::{self, ManuallyDrop},
+    ptr,
+};
+
+/// Builds a boxed slice in an allocator element by element.
+///
+/// The elements are written into a growing [`AllocatedBuffer`], so no temporary collection is
+/// needed. [`finish`] shrinks the buffer to the number of written elements and returns the boxed
+/// slice. If the builder is dropped instead, the written elements are dropped and the buffer is
+/// freed.
+///
+/// [`finish`]: Self::finish
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::System;
+/// use storages::boxed::BoxBuilder;
+///
+/// let mut builder = BoxBuilder::with_allocator(System);
+/// builder.extend((1..=3).chain(7..9));
+/// builder.push(10);
+///
+/// let values = builder.finish();
+///
+/// assert_eq!(*values, [1, 2, 3, 7, 8, 10]);
+/// ```
+pub struct BoxBuilder<T, A: Allocator = Global> {
+    slots: Box<[mem::MaybeUninit<T>], AllocatedBuffer<[T], A>, A>,
+    len: usize,
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> BoxBuilder<T> {
+    /// Creates an empty builder on the global heap.
+    pub fn new() -> Self {
+        Self::with_allocator(Global)
+    }
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> Default for BoxBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: Allocator> BoxBuilder<T, A> {
+    /// Creates an empty builder in the provided allocator.
+    pub fn with_allocator(allocator: A) -> Self {
+        Self::with_capacity_in(0, allocator)
+    }
+
+    /// Creates an empty builder in the provided allocator with space for at least `capacity`
+    /// elements.
+    pub fn with_capacity_in(capacity: usize, allocator: A) -> Self {
+        let buffer = AllocatedBuffer::new_slice(&allocator, capacity)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(capacity).unwrap()));
+        Self {
+            slots: Box::new_uninit_slice_in(buffer, allocator),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of written elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no element has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        let slots = &mut self.slots;
+        handle_reserve(
+            slots
+                .raw
+                .buffer_mut()
+                .try_reserve(&slots.data, self.len, additional),
+        );
+    }
+
+    /// Appends an element, growing the buffer if needed.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.slots.len() {
+            self.reserve(1);
+        }
+        self.slots[self.len] = mem::MaybeUninit::new(value);
+        self.len += 1;
+    }
+
+    /// Returns the boxed slice of the written elements.
+    ///
+    /// The buffer is shrunk to the number of written elements first.
+    pub fn finish(self) -> Box<[T], AllocatedBuffer<[T], A>, A> {
+        let this = ManuallyDrop::new(self);
+        let len = this.len;
+        let mut slots = unsafe { ptr::read(&this.slots) };
+        slots
+            .raw
+            .buffer_mut()
+            .shrink(&slots.data, len)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(len).unwrap()));
+        unsafe { slots.assume_init() }
+    }
+}
+
+impl<T, A: Allocator> Extend<T> for BoxBuilder<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for BoxBuilder<T, A> {
+    fn drop(&mut self) {
+        let written = ptr::slice_from_raw_parts_mut(self.slots.as_mut_ptr().cast::<T>(), self.len);
+        unsafe { ptr::drop_in_place(written) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Counting;
+    #[cfg(feature = "global")]
+    use alloc::rc::Rc;
+
+    #[test]
+    fn build_from_chained_iterator() {
+        let counting = Counting::default();
+        let mut builder = BoxBuilder::with_allocator(&counting);
+        builder.extend((0..3_u32).chain(10..12));
+        builder.extend(Some(20));
+        assert_eq!(builder.len(), 6);
+
+        let values = builder.finish();
+        assert_eq!(*values, [0, 1, 2, 10, 11, 20]);
+
+        drop(values);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn drop_unfinished() {
+        let value = Rc::new(());
+        let mut builder = BoxBuilder::new();
+        builder.push(Rc::clone(&value));
+        builder.push(Rc::clone(&value));
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(builder);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,154 @@
+use super::Box;
+use crate::buffer::{dangling, AllocatedBuffer};
+use alloc::alloc::{handle_alloc_error, Global};
+use core::{
+    alloc::{Allocator, Layout},
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+    slice,
+};
+
+/// A dynamically sized value, which consists of a header followed by a byte slice.
+///
+/// This is the layout of many wire formats, e.g. a packet with a fixed-size header and a variable
+/// length payload. Both parts live in a single allocation, see [`Box::new_dst`].
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+#[repr(C)]
+pub struct HeaderSlice<H> {
+    /// The fixed-size part in front of the bytes.
+    pub header: H,
+    /// The trailing bytes.
+    pub body: [u8],
+}
+
+/// Frees the memory of a partially constructed value, if initializing the body panics.
+struct DeallocGuard {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl Drop for DeallocGuard {
+    fn drop(&mut self) {
+        unsafe { Global.deallocate(self.ptr, self.layout) }
+    }
+}
+
+impl<H> Box<HeaderSlice<H>> {
+    /// Allocates a header followed by `body_len` bytes on the global heap.
+    ///
+    /// The combined layout is computed up-front, so only one allocation is made. `init` is called
+    /// with the uninitialized body, afterwards the header is written. If `init` panics, the memory
+    /// is freed and `header` is dropped.
+    ///
+    /// # Safety
+    ///
+    /// `init` must initialize every byte of the body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    /// use storages::boxed::{Box, HeaderSlice};
+    ///
+    /// struct Header {
+    ///     kind: u16,
+    ///     checksum: u8,
+    /// }
+    ///
+    /// let header = Header {
+    ///     kind: 7,
+    ///     checksum: 3,
+    /// };
+    /// let packet: Box<HeaderSlice<Header>> = unsafe {
+    ///     Box::new_dst(header, 3, |body| {
+    ///         for (byte, value) in body.iter_mut().zip(b"abc") {
+    ///             *byte = MaybeUninit::new(*value);
+    ///         }
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(packet.header.kind, 7);
+    /// assert_eq!(packet.header.checksum, 3);
+    /// assert_eq!(&packet.body, b"abc");
+    /// ```
+    pub unsafe fn new_dst<F>(header: H, body_len: usize, init: F) -> Self
+    where
+        F: FnOnce(&mut [MaybeUninit<u8>]),
+    {
+        let (layout, offset) = Layout::array::<u8>(body_len)
+            .and_then(|body| Layout::new::<H>().extend(body))
+            .expect("capacity overflow");
+        let layout = layout.pad_to_align();
+
+        let memory = if layout.size() == 0 {
+            dangling(layout)
+        } else {
+            Global
+                .allocate(layout)
+                .unwrap_or_else(|_| handle_alloc_error(layout))
+                .cast()
+        };
+
+        if layout.size() == 0 {
+            init(&mut []);
+        } else {
+            let guard = DeallocGuard {
+                ptr: memory,
+                layout,
+            };
+            let body = memory.as_ptr().add(offset).cast::<MaybeUninit<u8>>();
+            init(slice::from_raw_parts_mut(body, body_len));
+            mem::forget(guard);
+        }
+        memory.as_ptr().cast::<H>().write(header);
+
+        // A slice pointer has the same metadata as a pointer to a struct with a slice tail
+        let value = ptr::slice_from_raw_parts_mut(memory.as_ptr(), body_len) as *mut HeaderSlice<H>;
+        Self::from_buffer(
+            AllocatedBuffer::from_raw(NonNull::new_unchecked(value)),
+            Global,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn header_and_body() {
+        let header = Rc::new(5_u64);
+        let packet = unsafe {
+            Box::new_dst(Rc::clone(&header), 5, |body| {
+                for (i, byte) in body.iter_mut().enumerate() {
+                    *byte = MaybeUninit::new(i as u8);
+                }
+            })
+        };
+        assert_eq!(*packet.header, 5);
+        assert_eq!(packet.body, [0, 1, 2, 3, 4]);
+        assert_eq!(mem::size_of_val(&*packet), 16);
+        assert_eq!(Rc::strong_count(&header), 2);
+
+        drop(packet);
+        assert_eq!(Rc::strong_count(&header), 1);
+    }
+
+    #[test]
+    fn zero_sized() {
+        let packet = unsafe { Box::new_dst((), 0, |body| assert!(body.is_empty())) };
+        assert_eq!(mem::size_of_val(&*packet), 0);
+    }
+
+    #[test]
+    fn init_panics() {
+        let header = Rc::new(());
+        let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            Box::new_dst(Rc::clone(&header), 4, |_| panic!("init failed"))
+        }));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&header), 1);
+    }
+}
@@ -0,0 +1,79 @@
+use super::Box;
+use crate::buffer::AllocatedBuffer;
+use core::{alloc::Allocator, mem::ManuallyDrop, ptr};
+use std::error::Error;
+
+/// Converts an error into a boxed trait object.
+///
+/// # Examples
+///
+/// ```
+/// use std::{error::Error, fmt};
+/// use storages::boxed::Box;
+///
+/// #[derive(Debug)]
+/// struct MyError;
+///
+/// impl fmt::Display for MyError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("my error")
+///     }
+/// }
+///
+/// impl Error for MyError {}
+///
+/// let error: Box<dyn Error + Send + Sync> = Box::from(MyError);
+///
+/// assert_eq!(error.to_string(), "my error");
+/// ```
+#[cfg_attr(doc, doc(cfg(feature = "std")))]
+impl<'a, E: Error + Send + Sync + 'a> From<E> for Box<dyn Error + Send + Sync + 'a> {
+    fn from(error: E) -> Self {
+        Box::new(error)
+    }
+}
+
+#[cfg_attr(doc, doc(cfg(feature = "std")))]
+impl<A: Allocator> Box<dyn Error + Send + Sync, AllocatedBuffer<dyn Error + Send + Sync, A>, A> {
+    /// Attempts to downcast the box to a concrete type.
+    ///
+    /// If the boxed error is not of type `T`, the box is returned unchanged in `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::{error::Error, fmt};
+    /// use storages::boxed::Box;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct MyError(u32);
+    ///
+    /// impl fmt::Display for MyError {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         write!(f, "my error {}", self.0)
+    ///     }
+    /// }
+    ///
+    /// impl Error for MyError {}
+    ///
+    /// let error: Box<dyn Error + Send + Sync> = Box::from(MyError(5));
+    /// let error = error.downcast::<fmt::Error>().err().unwrap();
+    /// let error = error.downcast::<MyError>().ok().unwrap();
+    ///
+    /// assert_eq!(*error, MyError(5));
+    /// ```
+    pub fn downcast<T: Error + 'static>(self) -> Result<Box<T, AllocatedBuffer<T, A>, A>, Self> {
+        if self.is::<T>() {
+            unsafe {
+                let this = ManuallyDrop::new(self);
+                let buffer = ptr::read(this.raw.buffer());
+                Ok(Box::from_buffer(
+                    AllocatedBuffer::from_raw(buffer.into_raw().cast()),
+                    ptr::read(&this.data),
+                ))
+            }
+        } else {
+            Err(self)
+        }
+    }
+}
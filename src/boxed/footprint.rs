@@ -0,0 +1,105 @@
+use super::Box;
+use crate::buffer::AllocatedBuffer;
+use core::{alloc::Allocator, mem};
+
+/// Reports the heap memory owned by a value.
+///
+/// This is a debugging aid to inspect the memory usage of nested structures. Boxes report the
+/// size of their allocation plus the footprint of the boxed value, so the footprint of a tree of
+/// boxes is the sum of all allocations in the tree. Padding the allocator adds on top of the
+/// requested layout is not included.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::{Box, MemoryFootprint};
+///
+/// let rows = Box::<[Box<[u8]>]>::from([Box::<[u8]>::from([1, 2, 3]), Box::<[u8]>::from([4, 5])]);
+///
+/// let outer = 2 * std::mem::size_of::<Box<[u8]>>();
+/// assert_eq!(rows.footprint(), outer + 3 + 2);
+/// # }
+/// ```
+pub trait MemoryFootprint {
+    /// Returns the number of heap bytes owned by `self`, excluding the size of `self`.
+    fn footprint(&self) -> usize;
+}
+
+macro_rules! impl_no_footprint {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl MemoryFootprint for $ty {
+                fn footprint(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_no_footprint!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    str,
+);
+
+impl<T: MemoryFootprint> MemoryFootprint for [T] {
+    fn footprint(&self) -> usize {
+        self.iter().map(T::footprint).sum()
+    }
+}
+
+impl<T: MemoryFootprint, const N: usize> MemoryFootprint for [T; N] {
+    fn footprint(&self) -> usize {
+        self[..].footprint()
+    }
+}
+
+impl<T, A> MemoryFootprint for Box<T, AllocatedBuffer<T, A>, A>
+where
+    T: ?Sized + MemoryFootprint,
+    A: Allocator,
+{
+    fn footprint(&self) -> usize {
+        mem::size_of_val::<T>(self) + (**self).footprint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boxed::collect_in, testing::Counting};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn nested_slices() {
+        let counting = Counting::default();
+        let rows = (1..=4)
+            .map(|len| Box::try_from_slice_in(&[0_u16; 4][..len], &counting).unwrap())
+            .collect::<Vec<_>>();
+        let rows = collect_in(rows, &counting);
+        let scalar = Box::try_new_in(5_u64, &counting).ok().unwrap();
+
+        assert_eq!(scalar.footprint(), 8);
+        assert_eq!(rows.footprint() + scalar.footprint(), counting.bytes.get());
+
+        drop((rows, scalar));
+        assert_eq!(counting.bytes.get(), 0);
+    }
+}
@@ -0,0 +1,92 @@
+use core::{mem, ptr};
+
+/// A guard for initializing a boxed slice element by element.
+///
+/// The guard is returned by [`Box::init_guard`] and tracks how many elements have been written.
+/// When the guard is dropped before calling [`finish`], e.g. because of a panic, the elements
+/// written so far are dropped.
+///
+/// [`Box::init_guard`]: crate::boxed::Box::init_guard
+/// [`finish`]: Self::finish
+///
+/// # Examples
+///
+/// If initialization panics midway, only the written elements are dropped:
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use std::{panic, rc::Rc};
+/// use storages::boxed::Box;
+///
+/// let value = Rc::new(());
+/// let mut values = Box::<[Rc<()>]>::new_uninit_slice(3);
+///
+/// let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+///     let mut guard = values.init_guard();
+///     guard.push(Rc::clone(&value));
+///     guard.push(Rc::clone(&value));
+///     panic!("initialization failed");
+/// }));
+///
+/// assert!(result.is_err());
+/// assert_eq!(Rc::strong_count(&value), 1);
+/// # }
+/// ```
+pub struct InitGuard<'a, T> {
+    slice: &'a mut [mem::MaybeUninit<T>],
+    len: usize,
+}
+
+impl<'a, T> InitGuard<'a, T> {
+    pub(super) fn new(slice: &'a mut [mem::MaybeUninit<T>]) -> Self {
+        Self { slice, len: 0 }
+    }
+
+    /// Returns the number of written elements.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no element has been written yet.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes `value` to the next uninitialized element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all elements are already written.
+    pub fn push(&mut self, value: T) {
+        assert!(
+            self.len < self.slice.len(),
+            "the slice is already initialized"
+        );
+        self.slice[self.len] = mem::MaybeUninit::new(value);
+        self.len += 1;
+    }
+
+    /// Finishes the initialization, so the written elements are not dropped anymore.
+    ///
+    /// Afterwards, it's safe to call `assume_init` on the box.
+    ///
+    /// # Panics
+    ///
+    /// Panics if not all elements have been written. In this case, the written elements are
+    /// dropped.
+    pub fn finish(self) {
+        assert_eq!(
+            self.len,
+            self.slice.len(),
+            "the slice is not fully initialized"
+        );
+        mem::forget(self);
+    }
+}
+
+impl<T> Drop for InitGuard<'_, T> {
+    fn drop(&mut self) {
+        let written = ptr::slice_from_raw_parts_mut(self.slice.as_mut_ptr().cast::<T>(), self.len);
+        unsafe { ptr::drop_in_place(written) }
+    }
+}
@@ -0,0 +1,81 @@
+use super::Box;
+use crate::buffer::Buffer;
+use std::io::{self, Read};
+
+#[cfg_attr(doc, doc(cfg(feature = "std")))]
+impl<B, D> Box<[u8], B, D>
+where
+    B: Buffer<[u8], ExternalData = D>,
+{
+    /// Reads from `reader` into the boxed bytes until the box is full or the reader is exhausted.
+    ///
+    /// Returns the number of bytes read, the remaining bytes are left unchanged. Reads, which
+    /// were [interrupted], are retried. As the bytes are read directly into the box, no
+    /// intermediate buffer is needed, e.g. for a box returned by [`Box::from_layout`], which is
+    /// already zeroed on allocation.
+    ///
+    /// [interrupted]: io::ErrorKind::Interrupted
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::boxed::Box;
+    ///
+    /// let mut reader: &[u8] = b"hello";
+    /// let mut bytes = Box::<[u8]>::from([0; 8]);
+    ///
+    /// assert_eq!(bytes.read_from(&mut reader)?, 5);
+    /// assert_eq!(*bytes, *b"hello\0\0\0");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read_from<R: Read + ?Sized>(&mut self, reader: &mut R) -> io::Result<usize> {
+        let mut filled = 0;
+        while filled < self.len() {
+            match reader.read(&mut self[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(filled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads at most two bytes at a time and is interrupted before every read.
+    struct Chunked<'a> {
+        bytes: &'a [u8],
+        interrupted: bool,
+    }
+
+    impl Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupted = !self.interrupted;
+            if self.interrupted {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(self.bytes.len()).min(2);
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn read_in_chunks() {
+        let mut reader = Chunked {
+            bytes: b"network",
+            interrupted: false,
+        };
+        let mut bytes = Box::<[u8]>::from([0; 4]);
+        assert_eq!(bytes.read_from(&mut reader).unwrap(), 4);
+        assert_eq!(*bytes, *b"netw");
+
+        assert_eq!(bytes.read_from(&mut reader).unwrap(), 3);
+        assert_eq!(*bytes, *b"orkw");
+    }
+}
@@ -1,26 +1,146 @@
+mod bits;
+mod builder;
+#[cfg(feature = "global")]
+mod dst;
+#[cfg(feature = "std")]
+mod error;
+mod footprint;
+mod init;
+#[cfg(feature = "std")]
+mod io;
 mod raw;
 
-pub use self::raw::*;
+#[cfg(feature = "global")]
+pub use self::dst::HeaderSlice;
+pub use self::{
+    bits::BitBox,
+    builder::BoxBuilder,
+    footprint::MemoryFootprint,
+    init::InitGuard,
+    raw::*,
+};
 
-use crate::buffer::{AllocatedBuffer, Buffer, UnmanagedBuffer};
-use alloc::alloc::Global;
+#[cfg(feature = "global")]
+use crate::buffer::AlignedBuffer;
+use crate::buffer::{
+    AllocatedBuffer,
+    Buffer,
+    DropStrategy,
+    GrowableBuffer,
+    InfallibleAllocator,
+    InlineBuffer,
+    LayoutBuffer,
+    OnceBuffer,
+    ReadBuffer,
+    StrategyBuffer,
+    TrackedBuffer,
+    UnmanagedBuffer,
+};
+use alloc::{
+    alloc::{handle_alloc_error, Global},
+    collections::TryReserveError,
+    vec::Vec,
+};
 use core::{
+    alloc::{AllocError, Allocator, Layout},
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    convert::TryFrom,
+    hash::{Hash, Hasher},
     mem,
     ops::{CoerceUnsized, Deref, DerefMut},
-    ptr,
+    pin::Pin,
+    ptr::{self, NonNull},
+    slice,
+    str::{self, Utf8Error},
 };
 use mem::ManuallyDrop;
 
-pub struct Box<T, B = AllocatedBuffer<T>, D = <B as Buffer<T>>::ExternalData>
+pub struct Box<T, B = AllocatedBuffer<T>, D = <B as ReadBuffer<T>>::ExternalData>
 where
     T: ?Sized,
-    B: Buffer<T, ExternalData = D>,
+    B: ReadBuffer<T, ExternalData = D>,
 {
     raw: RawBox<T, B>,
     data: D,
 }
 
+/// A boxed slice, which stores up to `N` elements inline.
+pub type InlineBox<T, const N: usize> = Box<[T], InlineBuffer<T, N>, ()>;
+
+/// A box, which stores its value in memory allocated by `A`.
+pub type HeapBox<T, A = Global> = Box<T, AllocatedBuffer<T, A>, A>;
+
+/// Collects the elements of an iterator into a boxed slice in the provided allocator.
+///
+/// The size hint of the iterator is used to allocate up-front. If the iterator yields more
+/// elements, the buffer is grown as needed. Afterwards, the buffer is shrunk to the number of
+/// collected elements.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::System;
+/// use storages::boxed::collect_in;
+///
+/// let values = collect_in((1..=5).filter(|x| x % 2 == 1), System);
+///
+/// assert_eq!(*values, [1, 3, 5]);
+/// ```
+pub fn collect_in<T, A, I>(iter: I, allocator: A) -> Box<[T], AllocatedBuffer<[T], A>, A>
+where
+    A: Allocator,
+    I: IntoIterator<Item = T>,
+{
+    let iter = iter.into_iter();
+    let mut builder = BoxBuilder::with_capacity_in(iter.size_hint().0, allocator);
+    builder.extend(iter);
+    builder.finish()
+}
+
+/// Collects the values of an iterator of results into a boxed slice on the global heap.
+///
+/// Collecting stops at the first error, which is returned after dropping the values collected
+/// so far.
+///
+/// # Examples
+///
+/// ```
+/// use storages::boxed::try_collect_box;
+///
+/// let values = try_collect_box("1 2 3".split(' ').map(str::parse::<u32>));
+/// assert_eq!(*values.ok().unwrap(), [1, 2, 3]);
+///
+/// let values = try_collect_box("1 x 3".split(' ').map(str::parse::<u32>));
+/// assert!(values.is_err());
+/// ```
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+pub fn try_collect_box<T, E, I>(iter: I) -> Result<Box<[T]>, E>
+where
+    I: IntoIterator<Item = Result<T, E>>,
+{
+    let iter = iter.into_iter();
+    let mut builder = BoxBuilder::with_capacity_in(iter.size_hint().0, Global);
+    for value in iter {
+        builder.push(value?);
+    }
+    Ok(builder.finish())
+}
+
+pub(crate) fn handle_reserve(result: Result<(), TryReserveError>) {
+    match result {
+        Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+        Err(TryReserveError::AllocError { layout, .. }) => handle_alloc_error(layout),
+        Ok(()) => {}
+    }
+}
+
 /// Construction of boxed values with a buffer backed by the global allocator.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
 #[allow(clippy::use_self)]
 impl<T> Box<T> {
     /// Allocates memory on the global heap and then places `value` into it.
@@ -51,14 +171,10 @@ impl<T> Box<T> {
     /// ```
     /// use storages::boxed::Box;
     ///
-    /// let mut five = Box::<u32>::new_uninit();
-    ///
-    /// let five = unsafe {
-    ///     // Deferred initialization:
-    ///     five.as_mut_ptr().write(5);
+    /// let five = Box::<u32>::new_uninit();
     ///
-    ///     five.assume_init()
-    /// };
+    /// // Deferred initialization:
+    /// let five = Box::write(five, 5);
     ///
     /// assert_eq!(*five, 5);
     /// ```
@@ -94,9 +210,85 @@ impl<T> Box<T> {
             data: Global,
         }
     }
+
+    /// Moves a possibly uninitialized value to the heap.
+    ///
+    /// The bytes are copied as they are, so initialization may be finished in the box before
+    /// calling [`assume_init`].
+    ///
+    /// [`assume_init`]: Box::assume_init
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    /// use storages::boxed::Box;
+    ///
+    /// let mut bytes = MaybeUninit::<[u8; 8]>::uninit();
+    /// let ptr = bytes.as_mut_ptr().cast::<u8>();
+    /// for i in 0..4 {
+    ///     unsafe { ptr.add(i).write(i as u8) };
+    /// }
+    ///
+    /// let mut bytes = Box::from_maybe_uninit(bytes);
+    /// let ptr = bytes.as_mut_ptr().cast::<u8>();
+    /// for i in 4..8 {
+    ///     unsafe { ptr.add(i).write(i as u8) };
+    /// }
+    /// let bytes = unsafe { bytes.assume_init() };
+    ///
+    /// assert_eq!(*bytes, [0, 1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[inline]
+    pub fn from_maybe_uninit(
+        value: mem::MaybeUninit<T>,
+    ) -> Box<mem::MaybeUninit<T>, AllocatedBuffer<T>> {
+        let mut boxed = Self::new_uninit();
+        *boxed = value;
+        boxed
+    }
+
+    /// Allocates memory on the global heap and initializes the value in place by calling `init`.
+    ///
+    /// Unlike [`new`], the value is never constructed on the stack, so this is suitable for
+    /// types, which are too large for the stack.
+    ///
+    /// [`new`]: Box::new
+    ///
+    /// # Safety
+    ///
+    /// `init` must fully initialize the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::boxed::Box;
+    ///
+    /// let values = unsafe {
+    ///     Box::<[u32; 1024]>::new_boxed(|uninit| {
+    ///         let ptr = uninit.as_mut_ptr().cast::<u32>();
+    ///         for i in 0..1024 {
+    ///             ptr.add(i).write(i as u32);
+    ///         }
+    ///     })
+    /// };
+    ///
+    /// assert_eq!(values[1023], 1023);
+    /// ```
+    #[inline]
+    pub unsafe fn new_boxed<F>(init: F) -> Self
+    where
+        F: FnOnce(&mut mem::MaybeUninit<T>),
+    {
+        let mut boxed = Self::new_uninit();
+        init(&mut boxed);
+        boxed.assume_init()
+    }
 }
 
 /// Construction of boxed slices with a buffer backed by the global allocator.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
 #[allow(clippy::use_self)]
 impl<T> Box<[T]> {
     /// Constructs a boxed slice with uninitialized contents.
@@ -152,6 +344,151 @@ impl<T> Box<[T]> {
     }
 }
 
+/// Conversion of boxed slices with a buffer backed by the global allocator.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> Box<[T]> {
+    /// Collects at most `max_len` elements of an iterator into a boxed slice.
+    ///
+    /// Memory for `max_len` elements is allocated up-front, so no reallocation happens while
+    /// collecting. Afterwards, the buffer is shrunk to the number of collected elements. If the
+    /// iterator yields more than `max_len` elements, the remaining elements are not consumed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::boxed::Box;
+    ///
+    /// let values = Box::<[u32]>::from_iter_bounded(4, (1..).take_while(|x| x * x < 10));
+    ///
+    /// assert_eq!(*values, [1, 2, 3]);
+    /// ```
+    pub fn from_iter_bounded<I>(max_len: usize, iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let buffer = AllocatedBuffer::new_slice_exact(&Global, max_len)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(max_len).unwrap()));
+        let mut boxed = Box::<[T], _, _>::new_uninit_slice_in(buffer, Global);
+
+        let mut len = 0;
+        for (slot, value) in boxed.iter_mut().zip(iter) {
+            *slot = mem::MaybeUninit::new(value);
+            len += 1;
+        }
+
+        boxed
+            .raw
+            .buffer_mut()
+            .shrink(&boxed.data, len)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(len).unwrap()));
+        unsafe { boxed.assume_init() }
+    }
+
+    /// Converts the boxed slice into a [`Vec`] without copying or reallocating.
+    ///
+    /// The capacity of the vector equals the length of the boxed slice.
+    ///
+    /// [`Vec`]: alloc::vec::Vec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::boxed::Box;
+    ///
+    /// let values = unsafe { Box::<[i32]>::new_zeroed_slice(3).assume_init() };
+    /// let mut values = values.into_vec();
+    /// values.push(1);
+    ///
+    /// assert_eq!(values, [0, 0, 0, 1]);
+    /// ```
+    pub fn into_vec(self) -> Vec<T> {
+        let mut this = ManuallyDrop::new(self);
+        let len = this.len();
+        unsafe { Vec::from_raw_parts(this.as_mut_ptr(), len, len) }
+    }
+}
+
+/// Construction of boxed slices aligned to a cache line.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T: Default> Box<[T]> {
+    /// Allocates `len` default values on the global heap, aligned to a cache line of 64 bytes.
+    ///
+    /// Values in different boxes never share a cache line, so threads writing to them don't
+    /// contend with each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::AtomicUsize;
+    /// use storages::boxed::Box;
+    ///
+    /// let counters = Box::<[AtomicUsize]>::new_cache_aligned(4);
+    ///
+    /// assert_eq!(counters.len(), 4);
+    /// assert_eq!(counters.as_ptr() as usize % 64, 0);
+    /// ```
+    pub fn new_cache_aligned(len: usize) -> Box<[T], AlignedBuffer<T, Global, 64>, Global> {
+        let buffer = AlignedBuffer::new_slice_in(&Global, len).unwrap_or_else(|_| {
+            let layout = Layout::array::<T>(len).and_then(|layout| layout.align_to(64));
+            handle_alloc_error(layout.expect("capacity overflow"))
+        });
+        let mut boxed = Box::<[T], _, _>::new_uninit_slice_in(buffer, Global);
+        let mut guard = boxed.init_guard();
+        for _ in 0..len {
+            guard.push(T::default());
+        }
+        guard.finish();
+        unsafe { boxed.assume_init() }
+    }
+}
+
+/// Fallible construction of boxed slices by cloning.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T: Clone> Box<[T]> {
+    /// Clones the elements of `src` into a new boxed slice on the global heap.
+    ///
+    /// Unlike `From<&[T]>`, this returns an error if the allocation fails. If cloning an element
+    /// panics, the elements cloned so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use storages::boxed::Box;
+    ///
+    /// let values = Box::try_from_slice(&[1, 2, 3])?;
+    ///
+    /// assert_eq!(*values, [1, 2, 3]);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn try_from_slice(src: &[T]) -> Result<Self, AllocError> {
+        Self::try_from_slice_in(src, Global)
+    }
+}
+
+impl<T: Clone, A: Allocator> Box<[T], AllocatedBuffer<[T], A>, A> {
+    /// Clones the elements of `src` into a new boxed slice in the provided allocator.
+    ///
+    /// See [`try_from_slice`] for details.
+    ///
+    /// [`try_from_slice`]: Box::try_from_slice
+    pub fn try_from_slice_in(src: &[T], allocator: A) -> Result<Self, AllocError> {
+        let buffer = AllocatedBuffer::new_slice_exact(&allocator, src.len())?;
+        let mut boxed = Box::<[T], _, _>::new_uninit_slice_in(buffer, allocator);
+
+        let mut guard = boxed.init_guard();
+        for value in src {
+            guard.push(value.clone());
+        }
+        guard.finish();
+        unsafe { Ok(boxed.assume_init()) }
+    }
+}
+
 /// Construction of boxed values in a provided buffer.
 #[allow(clippy::use_self)]
 impl<T, B, D> Box<T, B, D>
@@ -217,9 +554,99 @@ where
     }
 }
 
+impl<T, const N: usize> InlineBox<T, N> {
+    /// Creates a boxed slice of the initialized elements of an inline buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::{boxed::InlineBox, buffer::InlineBuffer};
+    ///
+    /// let mut buffer = InlineBuffer::new();
+    /// buffer.try_push(1).unwrap();
+    /// buffer.try_push(2).unwrap();
+    ///
+    /// let values: InlineBox<u32, 4> = InlineBox::from_inline(buffer);
+    ///
+    /// assert_eq!(*values, [1, 2]);
+    /// ```
+    pub fn from_inline(buffer: InlineBuffer<T, N>) -> Self {
+        // `InlineBuffer` only exposes its initialized elements
+        unsafe { Self::from_buffer(buffer, ()) }
+    }
+}
+
+impl<T, A: Allocator> HeapBox<T, A> {
+    /// Allocates memory in the provided allocator and then places `value` into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::boxed::HeapBox;
+    ///
+    /// let five: HeapBox<u32, System> = HeapBox::try_new_in(5, System)?;
+    ///
+    /// assert_eq!(*five, 5);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn try_new_in(value: T, allocator: A) -> Result<Self, AllocError> {
+        let buffer = AllocatedBuffer::new_in(&allocator)?;
+        Ok(Self::new_in(value, buffer, allocator))
+    }
+}
+
+/// Construction of boxed values in an allocator, which never fails.
+#[allow(clippy::use_self)]
+impl<T, A: InfallibleAllocator> Box<T, AllocatedBuffer<T, A>, A> {
+    /// Allocates memory in the provided allocator and then places `value` into it.
+    ///
+    /// As the allocator never fails, this doesn't return a `Result`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::{
+    ///     alloc::{handle_alloc_error, AllocError, Allocator, Global, Layout},
+    ///     ptr::NonNull,
+    /// };
+    /// use storages::{boxed::Box, buffer::InfallibleAllocator};
+    ///
+    /// struct Aborting;
+    ///
+    /// unsafe impl Allocator for Aborting {
+    ///     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    ///         Ok(Global
+    ///             .allocate(layout)
+    ///             .unwrap_or_else(|_| handle_alloc_error(layout)))
+    ///     }
+    ///
+    ///     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+    ///         Global.deallocate(ptr, layout)
+    ///     }
+    /// }
+    ///
+    /// impl InfallibleAllocator for Aborting {}
+    ///
+    /// let five = Box::new_in_infallible(5, Aborting);
+    ///
+    /// assert_eq!(*five, 5);
+    /// ```
+    #[inline]
+    pub fn new_in_infallible(value: T, allocator: A) -> Self {
+        let buffer = AllocatedBuffer::new_in(&allocator)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::new::<T>()));
+        Self::new_in(value, buffer, allocator)
+    }
+}
+
 impl<T: ?Sized, B, D> Box<T, B, D>
 where
-    B: Buffer<T, ExternalData = D>,
+    B: ReadBuffer<T, ExternalData = D>,
 {
     /// Creates a box from the provided buffer.
     ///
@@ -244,6 +671,79 @@ where
             data,
         }
     }
+
+    /// Separates the external data from the box, returning a [`RawBox`] and the data.
+    ///
+    /// As `RawBox` doesn't implement [`Drop`], the buffer is not freed anymore when it goes out
+    /// of scope, so it's up to the caller to free it with the returned data. This is the inverse
+    /// of [`RawBox::into_box`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::{boxed::Box, buffer::AllocatedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::new_in(&System)?;
+    /// let five = Box::new_in(5, buffer, System);
+    ///
+    /// let (five, allocator) = five.into_raw_box();
+    /// assert_eq!(*five.as_ref(&allocator), 5);
+    ///
+    /// five.free(&allocator);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn into_raw_box(self) -> (RawBox<T, B>, D) {
+        let this = ManuallyDrop::new(self);
+        unsafe { (ptr::read(&this.raw), ptr::read(&this.data)) }
+    }
+
+    /// Returns a reference to the external data, which is passed to the buffer, e.g. the
+    /// allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::{boxed::Box, buffer::AllocatedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::new_in(&System)?;
+    /// let five = Box::new_in(5, buffer, System);
+    ///
+    /// let _allocator: &System = five.external_data();
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn external_data(&self) -> &D {
+        &self.data
+    }
+
+    /// Returns `true` if both boxes point to the same value.
+    ///
+    /// Only the addresses are compared, metadata like the length of a slice is ignored. This is
+    /// an associated function, so it doesn't shadow a method of the boxed value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let a = Box::new(5);
+    /// let b = Box::new(5);
+    ///
+    /// assert!(Box::ptr_eq(&a, &a));
+    /// assert!(!Box::ptr_eq(&a, &b));
+    /// # }
+    /// ```
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        let this: *const T = &**this;
+        let other: *const T = &**other;
+        this.cast::<u8>() == other.cast::<u8>()
+    }
 }
 
 /// Construction of boxed slices in a provided buffer.
@@ -286,6 +786,30 @@ where
     }
 }
 
+impl<T, B, D> Box<[T], B, D>
+where
+    B: ReadBuffer<[T], ExternalData = D>,
+{
+    /// Returns a reference to the elements as an array, if the boxed slice has exactly `N`
+    /// elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let header = Box::<[u8]>::from(*b"GIF8");
+    ///
+    /// assert_eq!(header.as_array::<4>(), Some(b"GIF8"));
+    /// assert_eq!(header.as_array::<3>(), None);
+    /// # }
+    /// ```
+    pub fn as_array<const N: usize>(&self) -> Option<&[T; N]> {
+        <&[T; N]>::try_from(&**self).ok()
+    }
+}
+
 #[allow(clippy::use_self)]
 impl<T, B, D> Box<mem::MaybeUninit<T>, B, D>
 where
@@ -304,6 +828,7 @@ where
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(feature = "global")] {
     /// use storages::boxed::Box;
     ///
     /// let mut five = Box::<u32>::new_uninit();
@@ -315,6 +840,7 @@ where
     ///     five.assume_init()
     /// };
     /// assert_eq!(*five, 5);
+    /// # }
     /// ```
     #[inline]
     pub unsafe fn assume_init(self) -> Box<T, B, D> {
@@ -324,6 +850,31 @@ where
             data: ptr::read(&this.data),
         }
     }
+
+    /// Writes `value` into the box and converts it to `Box<T, B>`.
+    ///
+    /// This is a safe shortcut for writing through [`as_mut_ptr`] and calling
+    /// [`assume_init`].
+    ///
+    /// [`as_mut_ptr`]: core::mem::MaybeUninit::as_mut_ptr
+    /// [`assume_init`]: Self::assume_init
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let five = Box::write(Box::<u32>::new_uninit(), 5);
+    ///
+    /// assert_eq!(*five, 5);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn write(mut boxed: Self, value: T) -> Box<T, B, D> {
+        *boxed = mem::MaybeUninit::new(value);
+        unsafe { boxed.assume_init() }
+    }
 }
 
 #[allow(clippy::use_self)]
@@ -369,63 +920,2183 @@ where
             data: ptr::read(&this.data),
         }
     }
-}
-
-#[doc(hidden)]
-impl<T: ?Sized, S: Buffer<T, ExternalData = D>, D> Drop for Box<T, S, D> {
-    default fn drop(&mut self) {
-        // buffer is managed, no drop needed
-    }
-}
 
-impl<T, S, D> Drop for Box<T, S, D>
-where
-    T: ?Sized,
-    S: UnmanagedBuffer<T, ExternalData = D>,
-{
-    fn drop(&mut self) {
-        unsafe { self.raw.buffer_mut().free_unchecked(&self.data) }
-    }
+    /// Converts to `Box<[T], B, D>` like [`assume_init`], but checks every element with
+    /// `is_init` in debug builds first.
+    ///
+    /// This is a debugging aid for catching missed initializations, e.g. for types with a
+    /// sentinel value like `0` for zeroed slices. In release builds, `is_init` is not called.
+    ///
+    /// [`assume_init`]: Self::assume_init
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`assume_init`]. Passing the check doesn't prove that the elements are
+    /// initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `is_init` returns `false` for any element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use std::mem;
+    /// use storages::boxed::Box;
+    ///
+    /// let mut values = Box::<[u32]>::new_zeroed_slice(2);
+    /// values[0] = mem::MaybeUninit::new(1);
+    /// values[1] = mem::MaybeUninit::new(2);
+    ///
+    /// let values = unsafe { values.assume_init_checked(|value| value.as_ptr().read() != 0) };
+    ///
+    /// assert_eq!(*values, [1, 2]);
+    /// # }
+    /// ```
+    pub unsafe fn assume_init_checked<F>(self, mut is_init: F) -> Box<[T], B, D>
+    where
+        F: FnMut(&mem::MaybeUninit<T>) -> bool,
+    {
+        if cfg!(debug_assertions) {
+            for (index, element) in self.iter().enumerate() {
+                assert!(is_init(element), "element {} is not initialized", index);
+            }
+        }
+        self.assume_init()
+    }
 }
 
-impl<T, B, D> Deref for Box<T, B, D>
+impl<T, B, D> Box<[mem::MaybeUninit<T>], B, D>
 where
-    T: ?Sized,
-    B: Buffer<T, ExternalData = D>,
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
 {
-    type Target = T;
+    /// Returns a guard for initializing the elements in order.
+    ///
+    /// If the guard is dropped before calling [`InitGuard::finish`], e.g. because of a panic,
+    /// the elements written so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let mut values = Box::<[String]>::new_uninit_slice(2);
+    ///
+    /// let mut guard = values.init_guard();
+    /// guard.push(String::from("a"));
+    /// guard.push(String::from("b"));
+    /// guard.finish();
+    ///
+    /// let values = unsafe { values.assume_init() };
+    ///
+    /// assert_eq!(*values, ["a", "b"]);
+    /// # }
+    /// ```
+    pub fn init_guard(&mut self) -> InitGuard<'_, T> {
+        InitGuard::new(self)
+    }
 
-    fn deref(&self) -> &Self::Target {
-        self.raw.as_ref(&self.data)
+    /// Initializes every element with the result of `f`, which is called with the index of the
+    /// element.
+    ///
+    /// If `f` returns an error, the elements written so far are dropped, so the box is left
+    /// uninitialized, and the error is returned. On success, it's safe to call `assume_init`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use std::convert::TryFrom;
+    /// use storages::boxed::Box;
+    ///
+    /// let mut values = Box::<[u8]>::new_uninit_slice(3);
+    /// values.try_init_each(|index| u8::try_from(index * 100))?;
+    ///
+    /// let values = unsafe { values.assume_init() };
+    /// assert_eq!(*values, [0, 100, 200]);
+    ///
+    /// let mut values = Box::<[u8]>::new_uninit_slice(4);
+    /// assert!(
+    ///     values
+    ///         .try_init_each(|index| u8::try_from(index * 100))
+    ///         .is_err()
+    /// );
+    /// # }
+    /// # Ok::<(), core::num::TryFromIntError>(())
+    /// ```
+    pub fn try_init_each<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(usize) -> Result<T, E>,
+    {
+        let len = self.len();
+        let mut guard = self.init_guard();
+        for index in 0..len {
+            guard.push(f(index)?);
+        }
+        guard.finish();
+        Ok(())
     }
 }
 
-impl<T, B, D> DerefMut for Box<T, B, D>
+impl<T, B, D> Box<[mem::MaybeUninit<T>], B, D>
 where
-    T: ?Sized,
-    B: Buffer<T, ExternalData = D>,
+    T: Copy,
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
 {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.raw.as_mut(&self.data)
+    /// Returns the uninitialized contents as bytes.
+    ///
+    /// The returned slice has a length of `len * mem::size_of::<T>()` bytes and, as any byte
+    /// slice, an alignment of `1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let values = Box::<[u16]>::new_zeroed_slice(3);
+    ///
+    /// assert_eq!(values.as_uninit_bytes().len(), 6);
+    /// # }
+    /// ```
+    pub fn as_uninit_bytes(&self) -> &[mem::MaybeUninit<u8>] {
+        let len = mem::size_of_val::<[mem::MaybeUninit<T>]>(self);
+        unsafe { slice::from_raw_parts(self.as_ptr().cast(), len) }
+    }
+
+    /// Returns the uninitialized contents as mutable bytes, e.g. for reading data into the box
+    /// before calling [`assume_init`].
+    ///
+    /// The returned slice has a length of `len * mem::size_of::<T>()` bytes and, as any byte
+    /// slice, an alignment of `1`.
+    ///
+    /// [`assume_init`]: Box::assume_init
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use std::mem;
+    /// use storages::boxed::Box;
+    ///
+    /// let mut values = Box::<[u8]>::new_uninit_slice(4);
+    ///
+    /// for (byte, &value) in values.as_uninit_bytes_mut().iter_mut().zip(b"data") {
+    ///     *byte = mem::MaybeUninit::new(value);
+    /// }
+    /// let values = unsafe { values.assume_init() };
+    ///
+    /// assert_eq!(*values, *b"data");
+    /// # }
+    /// ```
+    pub fn as_uninit_bytes_mut(&mut self) -> &mut [mem::MaybeUninit<u8>] {
+        let len = mem::size_of_val::<[mem::MaybeUninit<T>]>(self);
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr().cast(), len) }
     }
 }
 
-impl<T, U, D, BT, BU> CoerceUnsized<Box<U, BU, D>> for Box<T, BT, D>
+impl<T, B, D> Box<[mem::MaybeUninit<T>], B, D>
 where
-    T: ?Sized,
-    U: ?Sized,
-    BT: Buffer<T, ExternalData = D> + CoerceUnsized<BU>,
-    BU: Buffer<U, ExternalData = D>,
+    B: GrowableBuffer<[mem::MaybeUninit<T>], ExternalData = D>,
 {
+    /// Grows the boxed slice, so it can hold at least `additional` more elements.
+    ///
+    /// As a box doesn't distinguish between its length and its capacity, the boxed slice spans
+    /// the whole grown buffer afterwards, and the new elements are uninitialized. The capacity is
+    /// at least doubled to amortize consecutive reservations. Buffers with a fixed capacity fail
+    /// with [`TryReserveError::CapacityOverflow`] instead. Collections, which track their length
+    /// separately, should use [`GrowableBuffer::try_reserve`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::{alloc::System, mem};
+    /// use storages::{boxed::Box, buffer::AllocatedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::new_slice(&System, 2)?;
+    /// let mut values = Box::<[u32], _>::new_uninit_slice_in(buffer, System);
+    /// values[0] = mem::MaybeUninit::new(1);
+    /// values[1] = mem::MaybeUninit::new(2);
+    ///
+    /// values.try_reserve(3)?;
+    ///
+    /// assert!(values.len() >= 5);
+    /// assert_eq!(unsafe { values[1].assume_init() }, 2);
+    /// # Ok::<(), std::boxed::Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let len = self.len();
+        GrowableBuffer::try_reserve(self.raw.buffer_mut(), &self.data, len, additional)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn new() {
-        let five = Box::new(5);
+impl<T, A: Allocator> Box<[mem::MaybeUninit<T>], AllocatedBuffer<[T], A>, A> {
+    /// Converts the first `len` elements to `T` and frees the remaining slots.
+    ///
+    /// This is useful if a boxed slice was only filled partially, e.g. from an iterator, which
+    /// yielded fewer elements than expected. The buffer is shrunk to `len` elements, so the
+    /// returned box doesn't hold on to the unused memory.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements must be initialized, see [`assume_init`].
+    ///
+    /// [`assume_init`]: Self::assume_init
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than the length of the boxed slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use std::mem::MaybeUninit;
+    /// use storages::boxed::Box;
+    ///
+    /// let mut values = Box::<[u32]>::new_uninit_slice(8);
+    /// for (slot, value) in values.iter_mut().zip("1 2 3".split(' ')) {
+    ///     *slot = MaybeUninit::new(value.parse()?);
+    /// }
+    /// let values = unsafe { values.assume_init_len(3) };
+    ///
+    /// assert_eq!(*values, [1, 2, 3]);
+    /// # }
+    /// # Ok::<(), core::num::ParseIntError>(())
+    /// ```
+    pub unsafe fn assume_init_len(mut self, len: usize) -> Box<[T], AllocatedBuffer<[T], A>, A> {
+        assert!(
+            len <= self.len(),
+            "`len` (is {}) should be <= the length of the boxed slice (is {})",
+            len,
+            self.len()
+        );
+        self.raw
+            .buffer_mut()
+            .shrink(&self.data, len)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(len).unwrap()));
+        self.assume_init()
+    }
+}
 
-        assert_eq!(*five, 5);
+impl<T: ?Sized, A: Allocator> Box<T, AllocatedBuffer<T, A>, A> {
+    /// Converts the box into a pinned box without reallocating.
+    ///
+    /// This is only provided for allocated buffers, as the boxed value stays at the same address
+    /// when the box is moved. Buffers storing the value inline, like arrays, can't be pinned this
+    /// way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let five = Box::new(5_u32);
+    /// let ptr: *const u32 = &*five;
+    ///
+    /// let five = Box::into_pin(five);
+    ///
+    /// assert_eq!(*five, 5);
+    /// assert_eq!(ptr, &*five);
+    /// # }
+    /// ```
+    pub fn into_pin(boxed: Self) -> Pin<Self> {
+        // The value lives in the allocation, which doesn't move together with the box.
+        unsafe { Pin::new_unchecked(boxed) }
+    }
+
+    /// Consumes and leaks the box, returning a mutable reference, `&'a mut T`.
+    ///
+    /// The allocation is never freed, neither is the allocator dropped. This is mainly useful for
+    /// data that lives for the remainder of the program's life, e.g. long-lived tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let values = unsafe { Box::<[u32]>::new_zeroed_slice(3).assume_init() };
+    /// let values: &'static mut [u32] = Box::leak(values);
+    /// values[1] = 5;
+    ///
+    /// assert_eq!(values, [0, 5, 0]);
+    /// # }
+    /// ```
+    pub fn leak<'a>(boxed: Self) -> &'a mut T
+    where
+        A: 'a,
+    {
+        let this = ManuallyDrop::new(boxed);
+        unsafe { &mut *ptr::read(this.raw.buffer()).into_raw().as_ptr() }
+    }
+}
+
+impl<T, A: Allocator> Box<T, AllocatedBuffer<T, A>, A> {
+    /// Reinterprets the boxed value as `U` without moving it.
+    ///
+    /// The allocation is reused, so the returned box will free the same memory.
+    ///
+    /// # Safety
+    ///
+    /// - `U` must have the same size and alignment as `T`, so the memory is freed with the
+    ///   layout it was allocated with
+    /// - the bytes of the boxed value must be a valid value of `U`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let value = Box::new(u32::MAX);
+    /// let value = unsafe { value.cast::<i32>() };
+    ///
+    /// assert_eq!(*value, -1);
+    /// # }
+    /// ```
+    pub unsafe fn cast<U>(self) -> Box<U, AllocatedBuffer<U, A>, A> {
+        debug_assert_eq!(Layout::new::<T>(), Layout::new::<U>());
+        let this = ManuallyDrop::new(self);
+        let buffer = ptr::read(this.raw.buffer());
+        Box::from_buffer(
+            AllocatedBuffer::from_raw(buffer.into_raw().cast()),
+            ptr::read(&this.data),
+        )
+    }
+
+    /// Wraps the boxed value in [`ManuallyDrop`] without moving it.
+    ///
+    /// The allocation is reused. Dropping the returned box frees the memory, but doesn't run the
+    /// destructor of the value. Use [`from_manually_drop`] to re-enable it.
+    ///
+    /// [`from_manually_drop`]: Box::from_manually_drop
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use std::mem::ManuallyDrop;
+    /// use storages::boxed::Box;
+    ///
+    /// let mut value = Box::new(vec![1, 2, 3]).into_manually_drop();
+    /// let vec = unsafe { ManuallyDrop::take(&mut *value) };
+    /// drop(value);
+    ///
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// # }
+    /// ```
+    pub fn into_manually_drop(
+        self,
+    ) -> Box<ManuallyDrop<T>, AllocatedBuffer<ManuallyDrop<T>, A>, A> {
+        // `ManuallyDrop<T>` is `repr(transparent)`, so it has the same layout and validity as `T`
+        unsafe { self.cast() }
+    }
+
+    /// Unwraps the [`ManuallyDrop`] value without moving it.
+    ///
+    /// This is the inverse of [`into_manually_drop`]: the destructor of the value runs again when
+    /// the returned box is dropped. Like [`ManuallyDrop::into_inner`], this is safe, as dropping
+    /// or taking out the value with [`ManuallyDrop::drop`] or [`ManuallyDrop::take`] already
+    /// requires that the value isn't used afterwards.
+    ///
+    /// [`into_manually_drop`]: Box::into_manually_drop
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let value = Box::new(5).into_manually_drop();
+    /// let value = Box::from_manually_drop(value);
+    ///
+    /// assert_eq!(*value, 5);
+    /// # }
+    /// ```
+    pub fn from_manually_drop(
+        boxed: Box<ManuallyDrop<T>, AllocatedBuffer<ManuallyDrop<T>, A>, A>,
+    ) -> Self {
+        // `ManuallyDrop<T>` is `repr(transparent)`, so it has the same layout and validity as `T`
+        unsafe { boxed.cast() }
+    }
+}
+
+impl<T, A: Allocator> Box<T, AllocatedBuffer<T, A>, A> {
+    /// Moves the boxed value into a new allocation in `new_allocator`.
+    ///
+    /// The old allocation is freed in the old allocator afterwards, e.g. to promote a value from
+    /// an arena to the global heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::alloc::System;
+    /// use storages::boxed::Box;
+    ///
+    /// let five = Box::new(5).realloc_in(System);
+    ///
+    /// assert_eq!(*five, 5);
+    /// # }
+    /// ```
+    pub fn realloc_in<A2: Allocator>(
+        self,
+        new_allocator: A2,
+    ) -> Box<T, AllocatedBuffer<T, A2>, A2> {
+        let mut boxed = Box::<T, _, _>::new_uninit_in(
+            AllocatedBuffer::new_in(&new_allocator)
+                .unwrap_or_else(|_| handle_alloc_error(Layout::new::<T>())),
+            new_allocator,
+        );
+        let (mut raw, allocator) = self.into_raw_box();
+        unsafe {
+            ptr::copy_nonoverlapping(raw.as_ref(&allocator), boxed.as_mut_ptr(), 1);
+            raw.free_unchecked(&allocator);
+            boxed.assume_init()
+        }
+    }
+}
+
+impl<T, A: Allocator> Box<[T], AllocatedBuffer<[T], A>, A> {
+    /// Moves the boxed elements into a new allocation in `new_allocator`.
+    ///
+    /// The old allocation is freed in the old allocator afterwards, e.g. to promote a slice from
+    /// an arena to the global heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::alloc::System;
+    /// use storages::boxed::Box;
+    ///
+    /// let values = Box::<[u32]>::from([1, 2, 3]).realloc_in(System);
+    ///
+    /// assert_eq!(*values, [1, 2, 3]);
+    /// # }
+    /// ```
+    pub fn realloc_in<A2: Allocator>(
+        self,
+        new_allocator: A2,
+    ) -> Box<[T], AllocatedBuffer<[T], A2>, A2> {
+        let len = self.len();
+        let buffer = AllocatedBuffer::new_slice_exact(&new_allocator, len)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(len).unwrap()));
+        let mut boxed = Box::<[T], _, _>::new_uninit_slice_in(buffer, new_allocator);
+        let (mut raw, allocator) = self.into_raw_box();
+        unsafe {
+            let src: *const [T] = raw.as_ref(&allocator);
+            ptr::copy_nonoverlapping(src.as_ptr(), boxed.as_mut_ptr().cast(), len);
+            raw.free_unchecked(&allocator);
+            boxed.assume_init()
+        }
+    }
+
+    /// Consumes the box and returns a pointer to the elements, the number of elements, and the
+    /// allocator.
+    ///
+    /// The memory is not freed, so it's up to the caller to free it, e.g. by passing the parts to
+    /// [`from_raw_parts`] again. This is useful to pass a slice over FFI.
+    ///
+    /// [`from_raw_parts`]: Self::from_raw_parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let values = Box::<[u32]>::from([1, 2, 3]);
+    /// let (ptr, len, allocator) = values.into_raw_parts();
+    /// assert_eq!(unsafe { *ptr.add(2) }, 3);
+    ///
+    /// let values = unsafe { Box::from_raw_parts(ptr, len, allocator) };
+    /// assert_eq!(*values, [1, 2, 3]);
+    /// # }
+    /// ```
+    pub fn into_raw_parts(self) -> (*mut T, usize, A) {
+        let this = ManuallyDrop::new(self);
+        let (buffer, allocator) = unsafe { (ptr::read(this.raw.buffer()), ptr::read(&this.data)) };
+        let elements = buffer.into_raw();
+        (elements.as_mut_ptr(), elements.len(), allocator)
+    }
+
+    /// Constructs a boxed slice from the parts returned by [`into_raw_parts`].
+    ///
+    /// [`into_raw_parts`]: Self::into_raw_parts
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to `len` initialized elements, which were allocated by `allocator` with
+    /// the layout of `[T; len]`. For zero-sized allocations, `ptr` may be dangling, but must be
+    /// non-null.
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize, allocator: A) -> Self {
+        let buffer = AllocatedBuffer::from_raw_parts(NonNull::new_unchecked(ptr), len);
+        Self::from_buffer(buffer, allocator)
+    }
+
+    /// Splits the boxed slice into two at the given index.
+    ///
+    /// The elements `[at, len)` are moved into a new allocation in a clone of the allocator and
+    /// returned, while this box is shrunk to the elements `[0, at)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let mut values = Box::<[u32]>::from([1, 2, 3, 4, 5]);
+    /// let tail = values.split_off(2);
+    ///
+    /// assert_eq!(*values, [1, 2]);
+    /// assert_eq!(*tail, [3, 4, 5]);
+    /// # }
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        let len = self.len();
+        assert!(
+            at <= len,
+            "`at` split index (is {}) should be <= len (is {})",
+            at,
+            len
+        );
+        let tail_len = len - at;
+        let buffer = AllocatedBuffer::new_slice_exact(&self.data, tail_len)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(tail_len).unwrap()));
+        let mut tail = Box::<[T], _, _>::new_uninit_slice_in(buffer, self.data.clone());
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr().add(at), tail.as_mut_ptr().cast(), tail_len);
+            // the moved elements must not be reachable through `self` anymore, so failing to
+            // shrink is not recoverable
+            self.raw
+                .buffer_mut()
+                .shrink(&self.data, at)
+                .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(at).unwrap()));
+            tail.assume_init()
+        }
+    }
+}
+
+impl<T, A: Allocator> Box<[T], TrackedBuffer<T, A>, A> {
+    /// Changes the length of the boxed slice to `new_len` without reallocating.
+    ///
+    /// This is the box-level counterpart of `Vec::set_len`. Neither the buffer is reallocated, nor
+    /// are elements dropped or initialized. The length may exceed the requested length up to the
+    /// capacity the allocator provided, e.g. after the elements were filled externally. The
+    /// buffer is freed with its capacity, so the length doesn't have to be restored before the
+    /// box is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The first `new_len` elements must be initialized. Elements in `new_len..len` are not
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_len` is greater than the capacity of the buffer, see
+    /// [`TrackedBuffer::allocation_info`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::Global;
+    /// use storages::{boxed::Box, buffer::TrackedBuffer};
+    ///
+    /// let buffer = TrackedBuffer::<u32>::new_slice_zeroed(&Global, 4)?;
+    /// let values = unsafe { Box::<[u32], _>::from_buffer(buffer, Global) };
+    ///
+    /// let values = unsafe { values.with_len(2) };
+    /// assert_eq!(*values, [0, 0]);
+    ///
+    /// let values = unsafe { values.with_len(4) };
+    /// assert_eq!(*values, [0; 4]);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub unsafe fn with_len(mut self, new_len: usize) -> Self {
+        self.raw.buffer_mut().set_len(new_len);
+        self
+    }
+}
+
+impl<A: Allocator> Box<[u8], LayoutBuffer<A>, A> {
+    /// Allocates zeroed bytes for `layout` in the provided allocator.
+    ///
+    /// The layout is stored in the buffer, so the memory is freed with the same size and
+    /// alignment it was allocated with. This is useful for data, whose layout is only known at
+    /// runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::{Global, Layout};
+    /// use storages::boxed::Box;
+    ///
+    /// let layout = Layout::from_size_align(12, 4).unwrap();
+    /// let bytes = Box::from_layout(layout, Global);
+    ///
+    /// assert_eq!(*bytes, [0; 12]);
+    /// assert_eq!(bytes.as_ptr() as usize % 4, 0);
+    /// ```
+    pub fn from_layout(layout: Layout, allocator: A) -> Self {
+        let buffer =
+            LayoutBuffer::new_in(layout, &allocator).unwrap_or_else(|_| handle_alloc_error(layout));
+        unsafe { Self::from_buffer(buffer, allocator) }
+    }
+}
+
+/// Byte-buffer operations for boxed byte slices.
+///
+/// These are associated functions, so they don't shadow the methods of the slice.
+impl<B, D> Box<[u8], B, D>
+where
+    B: Buffer<[u8], ExternalData = D>,
+{
+    /// Sets all bytes to `byte`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let mut bytes = Box::<[u8]>::from(&[1, 2, 3][..]);
+    /// Box::fill(&mut bytes, 7);
+    ///
+    /// assert_eq!(*bytes, [7, 7, 7]);
+    /// # }
+    /// ```
+    pub fn fill(this: &mut Self, byte: u8) {
+        <[u8]>::fill(this, byte);
+    }
+
+    /// Copies all bytes from `src`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has a different length than the boxed slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let mut bytes = Box::<[u8]>::from([0; 4]);
+    /// Box::copy_from(&mut bytes, b"data");
+    ///
+    /// assert_eq!(*bytes, *b"data");
+    /// # }
+    /// ```
+    pub fn copy_from(this: &mut Self, src: &[u8]) {
+        assert_eq!(
+            this.len(),
+            src.len(),
+            "source slice length does not match the boxed slice"
+        );
+        this.copy_from_slice(src);
+    }
+
+    /// Splits the bytes into chunks of `N` bytes and the remaining bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let bytes = Box::<[u8]>::from([1, 2, 3, 4, 5]);
+    /// let (chunks, remainder) = Box::as_chunks::<2>(&bytes);
+    ///
+    /// assert_eq!(chunks, [[1, 2], [3, 4]]);
+    /// assert_eq!(remainder, [5]);
+    /// # }
+    /// ```
+    pub fn as_chunks<const N: usize>(this: &Self) -> (&[[u8; N]], &[u8]) {
+        assert_ne!(N, 0, "chunk size must be non-zero");
+        let len = this.len() / N;
+        let (chunks, remainder) = this.split_at(len * N);
+        let chunks = unsafe { slice::from_raw_parts(chunks.as_ptr().cast(), len) };
+        (chunks, remainder)
+    }
+}
+
+impl<A: Allocator> Box<str, AllocatedBuffer<str, A>, A> {
+    /// Converts a boxed byte slice into a boxed string slice without copying.
+    ///
+    /// Returns an error and drops the bytes if they are not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let hello = Box::<str>::from_utf8(Box::<[u8]>::from(*b"hello"))?;
+    /// assert_eq!(&*hello, "hello");
+    ///
+    /// assert!(Box::<str>::from_utf8(Box::<[u8]>::from([0xFF])).is_err());
+    /// # }
+    /// # Ok::<(), core::str::Utf8Error>(())
+    /// ```
+    pub fn from_utf8(bytes: Box<[u8], AllocatedBuffer<[u8], A>, A>) -> Result<Self, Utf8Error> {
+        str::from_utf8(&bytes)?;
+        Ok(unsafe { Self::from_utf8_unchecked(bytes) })
+    }
+
+    /// Converts a boxed byte slice into a boxed string slice without checking its contents.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8.
+    pub unsafe fn from_utf8_unchecked(bytes: Box<[u8], AllocatedBuffer<[u8], A>, A>) -> Self {
+        let this = ManuallyDrop::new(bytes);
+        let buffer = ptr::read(this.raw.buffer()).into_raw();
+        // `str` has the same layout as `[u8]`
+        let string = NonNull::new_unchecked(buffer.as_ptr() as *mut str);
+        Self::from_buffer(AllocatedBuffer::from_raw(string), ptr::read(&this.data))
+    }
+
+    /// Converts the boxed string slice into a boxed byte slice without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let bytes = Box::<str>::from("hello").into_boxed_bytes();
+    ///
+    /// assert_eq!(*bytes, *b"hello");
+    /// # }
+    /// ```
+    pub fn into_boxed_bytes(self) -> Box<[u8], AllocatedBuffer<[u8], A>, A> {
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let buffer = ptr::read(this.raw.buffer()).into_raw();
+            let bytes = NonNull::new_unchecked(buffer.as_ptr() as *mut [u8]);
+            Box::from_buffer(AllocatedBuffer::from_raw(bytes), ptr::read(&this.data))
+        }
+    }
+}
+
+/// Releases the buffer of a box when the box is dropped.
+///
+/// Managed buffers like arrays release their contents themselves, while unmanaged buffers drop
+/// the boxed value and are freed afterwards.
+trait DropBuffer<T: ?Sized>: ReadBuffer<T> {
+    unsafe fn drop_buffer(&mut self, data: &Self::ExternalData);
+}
+
+impl<T: ?Sized, B: ReadBuffer<T>> DropBuffer<T> for B {
+    default unsafe fn drop_buffer(&mut self, _data: &Self::ExternalData) {
+        // buffer is managed, no drop needed
+    }
+}
+
+impl<T: ?Sized, B: UnmanagedBuffer<T>> DropBuffer<T> for B {
+    default unsafe fn drop_buffer(&mut self, data: &Self::ExternalData) {
+        self.free_unchecked(data)
+    }
+}
+
+impl<T: ?Sized, B: UnmanagedBuffer<T> + Buffer<T>> DropBuffer<T> for B {
+    default unsafe fn drop_buffer(&mut self, data: &Self::ExternalData) {
+        ptr::drop_in_place(self.as_mut_ptr(data));
+        self.free_unchecked(data)
+    }
+}
+
+impl<T, B, S> DropBuffer<T> for StrategyBuffer<B, S>
+where
+    T: ?Sized,
+    B: UnmanagedBuffer<T> + Buffer<T>,
+    S: DropStrategy,
+{
+    unsafe fn drop_buffer(&mut self, data: &Self::ExternalData) {
+        if S::DROP_VALUE {
+            ptr::drop_in_place(self.as_mut_ptr(data));
+        }
+        if S::FREE {
+            self.free_unchecked(data)
+        }
+    }
+}
+
+impl<T, A: Allocator> DropBuffer<T> for OnceBuffer<T, A> {
+    unsafe fn drop_buffer(&mut self, allocator: &A) {
+        // accessing the value would allocate it just to drop it
+        if self.is_allocated() {
+            ptr::drop_in_place(self.as_mut_ptr(allocator));
+        }
+        self.free_unchecked(allocator)
+    }
+}
+
+impl<T, A: Allocator> DropBuffer<mem::MaybeUninit<T>> for AllocatedBuffer<T, A> {
+    unsafe fn drop_buffer(&mut self, allocator: &A) {
+        UnmanagedBuffer::<T>::free_unchecked(self, allocator)
+    }
+}
+
+impl<T, A: Allocator> DropBuffer<[mem::MaybeUninit<T>]> for AllocatedBuffer<[T], A> {
+    unsafe fn drop_buffer(&mut self, allocator: &A) {
+        UnmanagedBuffer::<[T]>::free_unchecked(self, allocator)
+    }
+}
+
+impl<T, A: Allocator> DropBuffer<[mem::MaybeUninit<T>]> for TrackedBuffer<T, A> {
+    unsafe fn drop_buffer(&mut self, allocator: &A) {
+        UnmanagedBuffer::<[T]>::free_unchecked(self, allocator)
+    }
+}
+
+/// Drops the boxed value and frees the buffer afterwards, if the buffer is unmanaged.
+///
+/// Managed buffers, see [`ReadBuffer::IS_MANAGED`], release the value and their resources
+/// themselves, so nothing is done for them.
+///
+/// The buffer is freed while the external data is still valid, as `data` is a field of the box
+/// and is only dropped after this returns. For an allocator passed as external data, this means
+/// that the memory is deallocated before the allocator itself is dropped.
+impl<T, B, D> Drop for Box<T, B, D>
+where
+    T: ?Sized,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    fn drop(&mut self) {
+        if !B::IS_MANAGED {
+            unsafe { DropBuffer::<T>::drop_buffer(self.raw.buffer_mut(), &self.data) }
+        }
+    }
+}
+
+impl<T, B, D> Deref for Box<T, B, D>
+where
+    T: ?Sized,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.raw.as_ref(&self.data)
+    }
+}
+
+impl<T, B, D> DerefMut for Box<T, B, D>
+where
+    T: ?Sized,
+    B: Buffer<T, ExternalData = D>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.raw.as_mut(&self.data)
+    }
+}
+
+impl<T, B, D> Borrow<T> for Box<T, B, D>
+where
+    T: ?Sized,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T, B, D> BorrowMut<T> for Box<T, B, D>
+where
+    T: ?Sized,
+    B: Buffer<T, ExternalData = D>,
+{
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+/// Clones the elements of the slice into a new boxed slice on the global heap.
+///
+/// # Examples
+///
+/// ```
+/// use storages::boxed::Box;
+///
+/// let values = Box::<[u32]>::from(&[1, 2, 3][..]);
+///
+/// assert_eq!(*values, [1, 2, 3]);
+/// ```
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T: Clone> From<&[T]> for Box<[T]> {
+    fn from(src: &[T]) -> Self {
+        Self::try_from_slice(src)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(src.len()).unwrap()))
+    }
+}
+
+/// Copies a string slice into a boxed string slice on the global heap.
+///
+/// # Examples
+///
+/// ```
+/// use storages::boxed::Box;
+///
+/// let hello = Box::<str>::from("hello");
+///
+/// assert_eq!(hello.len(), 5);
+/// assert!(hello.starts_with("he"));
+/// ```
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl From<&str> for Box<str> {
+    fn from(src: &str) -> Self {
+        unsafe { Self::from_utf8_unchecked(Box::from(src.as_bytes())) }
+    }
+}
+
+/// Moves the elements of an array into a boxed slice on the global heap.
+///
+/// # Examples
+///
+/// ```
+/// use storages::boxed::Box;
+///
+/// let values = Box::<[i32]>::from([1, 2, 3]);
+///
+/// assert_eq!(*values, [1, 2, 3]);
+/// ```
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T, const N: usize> From<[T; N]> for Box<[T]> {
+    fn from(array: [T; N]) -> Self {
+        let mut boxed = Self::new_uninit_slice(N);
+        let array = mem::ManuallyDrop::new(array);
+        unsafe {
+            let dst = <[mem::MaybeUninit<T>]>::as_mut_ptr(&mut boxed);
+            ptr::copy_nonoverlapping(<[T]>::as_ptr(&*array), dst.cast(), N);
+            boxed.assume_init()
+        }
+    }
+}
+
+/// Provides a reference to the boxed value, so boxes can be passed to `impl AsRef<T>` APIs
+/// regardless of the buffer.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::Box;
+///
+/// fn checksum(bytes: impl AsRef<[u8]>) -> u32 {
+///     bytes.as_ref().iter().map(|&byte| u32::from(byte)).sum()
+/// }
+///
+/// let heap = unsafe { Box::<[u8]>::new_zeroed_slice(3).assume_init() };
+/// let array: Box<[u8], _> = unsafe { Box::from_buffer([1_u8, 2, 3], ()) };
+///
+/// assert_eq!(checksum(heap), 0);
+/// assert_eq!(checksum(array), 6);
+/// # }
+/// ```
+impl<T, B, D> AsRef<T> for Box<T, B, D>
+where
+    T: ?Sized,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T, B, D> AsMut<T> for Box<T, B, D>
+where
+    T: ?Sized,
+    B: Buffer<T, ExternalData = D>,
+{
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+/// Provides the bytes of a boxed string slice.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::Box;
+///
+/// let hello = Box::<str>::from("hello");
+/// let bytes: &[u8] = hello.as_ref();
+///
+/// assert_eq!(bytes, b"hello");
+/// # }
+/// ```
+impl<B, D> AsRef<[u8]> for Box<str, B, D>
+where
+    B: ReadBuffer<str, ExternalData = D>,
+{
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Compares the contents, regardless of the buffers backing the boxes.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::Box;
+///
+/// let heap = Box::<[u32]>::from(&[1, 2, 3][..]);
+/// let array = unsafe { Box::from_buffer([1, 2, 3], ()) };
+///
+/// assert!(heap == array);
+/// # }
+/// ```
+impl<T, B1, D1, B2, D2> PartialEq<Box<T, B2, D2>> for Box<T, B1, D1>
+where
+    T: ?Sized + PartialEq,
+    B1: ReadBuffer<T, ExternalData = D1>,
+    B2: ReadBuffer<T, ExternalData = D2>,
+{
+    fn eq(&self, other: &Box<T, B2, D2>) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+/// Compares the contents with a box from the standard library.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::Box;
+///
+/// assert!(Box::new(5) == std::boxed::Box::new(5));
+/// # }
+/// ```
+impl<T, B, D> PartialEq<alloc::boxed::Box<T>> for Box<T, B, D>
+where
+    T: ?Sized + PartialEq,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    fn eq(&self, other: &alloc::boxed::Box<T>) -> bool {
+        PartialEq::eq(&**self, &**other)
+    }
+}
+
+/// Compares the elements of a boxed slice with a vector from the standard library.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::Box;
+///
+/// let values = Box::<[i32]>::from([1, 2, 3]);
+///
+/// assert!(values == vec![1, 2, 3]);
+/// assert!(values != vec![1, 2]);
+/// # }
+/// ```
+impl<T, B, D> PartialEq<Vec<T>> for Box<[T], B, D>
+where
+    T: PartialEq,
+    B: ReadBuffer<[T], ExternalData = D>,
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        **self == **other
+    }
+}
+
+impl<T, B, D> Eq for Box<T, B, D>
+where
+    T: ?Sized + Eq,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+}
+
+/// Compares the contents, regardless of the buffers backing the boxes.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::boxed::Box;
+///
+/// let heap = Box::<[u32]>::from(&[1, 2, 3][..]);
+/// let array = unsafe { Box::from_buffer([1, 2, 4], ()) };
+///
+/// assert!(heap < array);
+/// # }
+/// ```
+impl<T, B1, D1, B2, D2> PartialOrd<Box<T, B2, D2>> for Box<T, B1, D1>
+where
+    T: ?Sized + PartialOrd,
+    B1: ReadBuffer<T, ExternalData = D1>,
+    B2: ReadBuffer<T, ExternalData = D2>,
+{
+    fn partial_cmp(&self, other: &Box<T, B2, D2>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(&**self, &**other)
+    }
+}
+
+impl<T, B, D> Ord for Box<T, B, D>
+where
+    T: ?Sized + Ord,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(&**self, &**other)
+    }
+}
+
+/// Hashes only the boxed value, so a box hashes like the value it borrows as.
+impl<T, B, D> Hash for Box<T, B, D>
+where
+    T: ?Sized + Hash,
+    B: ReadBuffer<T, ExternalData = D>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for Box<T, AllocatedBuffer<T, A>, A> {
+    /// Returns a new box with a `clone()` of this box's contents in a clone of its allocator.
+    fn clone(&self) -> Self {
+        let buffer = AllocatedBuffer::new_in(&self.data)
+            .unwrap_or_else(|_| handle_alloc_error(Layout::new::<T>()));
+        Self::new_in((**self).clone(), buffer, self.data.clone())
+    }
+
+    /// Copies `source`'s contents into `self` without creating a new allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "global")] {
+    /// use storages::boxed::Box;
+    ///
+    /// let x = Box::new(5);
+    /// let mut y = Box::new(10);
+    /// let yp: *const i32 = &*y;
+    ///
+    /// y.clone_from(&x);
+    ///
+    /// // The value is the same
+    /// assert!(x == y);
+    ///
+    /// // And no allocation occurred
+    /// assert_eq!(yp, &*y);
+    /// # }
+    /// ```
+    fn clone_from(&mut self, source: &Self) {
+        (**self).clone_from(&**source)
+    }
+}
+
+impl<T: Clone, A: Allocator + Clone> Clone for Box<[T], AllocatedBuffer<[T], A>, A> {
+    fn clone(&self) -> Self {
+        collect_in(self.iter().cloned(), self.data.clone())
+    }
+
+    /// Clones `source`'s elements into `self`.
+    ///
+    /// If both boxed slices have the same length, the allocation of `self` is reused, otherwise
+    /// `self` is replaced by a clone of `source`.
+    fn clone_from(&mut self, source: &Self) {
+        if self.len() == source.len() {
+            self.clone_from_slice(source)
+        } else {
+            *self = source.clone()
+        }
+    }
+}
+
+impl<T, U, D, BT, BU> CoerceUnsized<Box<U, BU, D>> for Box<T, BT, D>
+where
+    T: ?Sized,
+    U: ?Sized,
+    BT: ReadBuffer<T, ExternalData = D> + CoerceUnsized<BU>,
+    BU: ReadBuffer<U, ExternalData = D>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{Counting, Recording, Rounding};
+    use alloc::rc::Rc;
+    use core::{
+        alloc::AllocError,
+        cell::{Cell, UnsafeCell},
+        ptr::NonNull,
+    };
+
+    #[repr(C, align(16))]
+    struct Bump {
+        memory: UnsafeCell<[u8; 64]>,
+        offset: Cell<usize>,
+    }
+
+    unsafe impl Allocator for Bump {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let start = (self.offset.get() + layout.align() - 1) & !(layout.align() - 1);
+            let end = start + layout.size();
+            assert!(end <= 64, "bump allocator exhausted");
+            self.offset.set(end);
+            let ptr = unsafe { self.memory.get().cast::<u8>().add(start) };
+            Ok(NonNull::slice_from_raw_parts(
+                unsafe { NonNull::new_unchecked(ptr) },
+                layout.size(),
+            ))
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+    }
+
+    impl InfallibleAllocator for &Bump {}
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn new() {
+        let five = Box::new(5);
+
+        assert_eq!(*five, 5);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn unsized_struct_tail() {
+        struct Packet<T: ?Sized> {
+            header: u32,
+            body: T,
+        }
+
+        let packet: Box<Packet<[u8]>> = Box::new(Packet {
+            header: 1,
+            body: [2, 3, 4],
+        });
+
+        assert_eq!(packet.header, 1);
+        assert_eq!(packet.body, [2, 3, 4]);
+    }
+
+    #[test]
+    fn new_in_infallible() {
+        let bump = Bump {
+            memory: UnsafeCell::new([0; 64]),
+            offset: Cell::new(0),
+        };
+
+        let a = Box::new_in_infallible(1_u32, &bump);
+        let b = Box::new_in_infallible(2_u64, &bump);
+
+        assert_eq!(*a, 1);
+        assert_eq!(*b, 2);
+        assert_eq!(bump.offset.get(), 16);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn borrow_slice() {
+        fn sum(values: &impl Borrow<[u32]>) -> u32 {
+            values.borrow().iter().sum()
+        }
+
+        let mut key = Box::<[u8]>::new_uninit_slice(3);
+        for (slot, &byte) in key.iter_mut().zip(b"key") {
+            *slot = mem::MaybeUninit::new(byte);
+        }
+        let key = unsafe { key.assume_init() };
+
+        let mut map = alloc::collections::BTreeMap::new();
+        map.insert(key, 1);
+
+        assert_eq!(map.get(&b"key"[..]), Some(&1));
+        assert_eq!(map.get(&b"other"[..]), None);
+
+        let values = unsafe { Box::from_buffer([1_u32, 2, 3], ()) };
+        assert_eq!(sum(&values), 6);
+    }
+
+    #[test]
+    fn try_reserve() {
+        let counting = Counting::default();
+        let buffer = AllocatedBuffer::new_slice(&&counting, 4).unwrap();
+        let mut values = Box::<[u32], _>::new_uninit_slice_in(buffer, &counting);
+
+        values.try_reserve(2).unwrap();
+        assert_eq!(values.len(), 8);
+        assert_eq!(counting.grows.get(), 1);
+
+        values.try_reserve(0).unwrap();
+        assert_eq!(values.len(), 8);
+        assert_eq!(counting.grows.get(), 1);
+
+        assert_eq!(
+            values.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+    }
+
+    #[test]
+    fn deref_array_buffer() {
+        let mut values: Box<[u32], [u32; 3]> = unsafe { Box::from_buffer([1, 2, 3], ()) };
+
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.iter().sum::<u32>(), 6);
+
+        values[1] = 5;
+        assert_eq!(*values, [1, 5, 3]);
+    }
+
+    #[test]
+    fn collect_in_allocator() {
+        let counting = Counting::default();
+        let values = collect_in((0..13).filter(|x| x % 3 == 0), &counting);
+
+        assert_eq!(*values, [0, 3, 6, 9, 12]);
+        assert_eq!(counting.allocations.get(), 1);
+        assert!(counting.grows.get() > 0);
+        assert_eq!(counting.shrinks.get(), 1);
+    }
+
+    #[test]
+    fn drop_frees_allocation() {
+        let counting = Counting::default();
+
+        let value = Box::new_in(
+            1_u32,
+            AllocatedBuffer::new_in(&&counting).unwrap(),
+            &counting,
+        );
+        drop(value);
+        assert_eq!(counting.deallocations.get(), 1);
+
+        let values = collect_in(0..3_u32, &counting);
+        drop(values);
+        assert_eq!(counting.deallocations.get(), 2);
+        assert_eq!(counting.allocations.get(), 2);
+
+        let value =
+            Box::<u32, _>::new_uninit_in(AllocatedBuffer::new_in(&&counting).unwrap(), &counting);
+        drop(value);
+        assert_eq!(counting.deallocations.get(), 3);
+
+        let values = Box::<[u32], _>::new_uninit_slice_in(
+            AllocatedBuffer::new_slice(&&counting, 3).unwrap(),
+            &counting,
+        );
+        drop(values);
+        assert_eq!(counting.deallocations.get(), 4);
+        assert_eq!(counting.allocations.get(), 4);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn init_guard_drops_written_elements() {
+        let value = Rc::new(());
+        let mut values = Box::<[_]>::new_uninit_slice(3);
+
+        let mut guard = values.init_guard();
+        guard.push(Rc::clone(&value));
+        guard.push(Rc::clone(&value));
+        assert_eq!(guard.len(), 2);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(guard);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[should_panic(expected = "the slice is not fully initialized")]
+    fn init_guard_finish_incomplete() {
+        let mut values = Box::<[u32]>::new_uninit_slice(2);
+
+        let mut guard = values.init_guard();
+        guard.push(1);
+        guard.finish();
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn from_iter_bounded() {
+        let shorter = Box::<[u32]>::from_iter_bounded(4, 0..2);
+        assert_eq!(*shorter, [0, 1]);
+
+        let equal = Box::<[u32]>::from_iter_bounded(4, 0..4);
+        assert_eq!(*equal, [0, 1, 2, 3]);
+
+        let mut iter = 0..6;
+        let longer = Box::<[u32]>::from_iter_bounded(4, &mut iter);
+        assert_eq!(*longer, [0, 1, 2, 3]);
+        assert_eq!(iter.next(), Some(4));
+
+        let empty = Box::<[u32]>::from_iter_bounded(4, 0..0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn into_raw_box() {
+        let counting = Counting::default();
+        let value = Box::new_in(
+            1_u32,
+            AllocatedBuffer::new_in(&&counting).unwrap(),
+            &counting,
+        );
+
+        let (raw, allocator) = value.into_raw_box();
+        assert_eq!(counting.deallocations.get(), 0);
+
+        raw.free(&allocator);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn drop_order() {
+        struct Owning {
+            outstanding: Cell<usize>,
+        }
+
+        unsafe impl Allocator for Owning {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.outstanding.set(self.outstanding.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.outstanding.set(self.outstanding.get() - 1);
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        impl Drop for Owning {
+            fn drop(&mut self) {
+                assert_eq!(self.outstanding.get(), 0, "allocator dropped too early");
+            }
+        }
+
+        let value = Rc::new(());
+        let allocator = Owning {
+            outstanding: Cell::new(0),
+        };
+        let buffer = AllocatedBuffer::new_in(&allocator).unwrap();
+        let boxed = Box::new_in(Rc::clone(&value), buffer, allocator);
+        assert_eq!(boxed.external_data().outstanding.get(), 1);
+
+        drop(boxed);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn try_from_slice() {
+        let counting = Counting::default();
+        let values = Box::try_from_slice_in(&[1_u32, 2, 3][..], &counting).unwrap();
+
+        assert_eq!(*values, [1, 2, 3]);
+        assert_eq!(counting.allocations.get(), 1);
+    }
+
+    struct Failing;
+
+    unsafe impl Allocator for Failing {
+        fn allocate(&self, _layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn try_from_slice_alloc_error() {
+        assert!(Box::try_from_slice_in(&[1_u32, 2, 3][..], Failing).is_err());
+    }
+
+    #[test]
+    fn try_new_in_alloc_error() {
+        let value = Rc::new(());
+        assert!(HeapBox::try_new_in(Rc::clone(&value), Failing).is_err());
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let boxed = HeapBox::try_new_in(Rc::clone(&value), Global).ok().unwrap();
+        assert_eq!(Rc::strong_count(&value), 2);
+        drop(boxed);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn try_from_slice_clone_panic() {
+        struct Bomb<'a>(&'a Cell<usize>, bool);
+
+        impl Clone for Bomb<'_> {
+            fn clone(&self) -> Self {
+                assert!(!self.1, "bomb exploded");
+                Bomb(self.0, false)
+            }
+        }
+
+        impl Drop for Bomb<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let counting = Counting::default();
+        let src = [
+            Bomb(&drops, false),
+            Bomb(&drops, false),
+            Bomb(&drops, true),
+            Bomb(&drops, false),
+        ];
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            Box::try_from_slice_in(&src[..], &counting)
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn clone_from_reuses_allocation() {
+        let counting = Counting::default();
+        let source = Box::new_in(
+            1_u32,
+            AllocatedBuffer::new_in(&&counting).unwrap(),
+            &counting,
+        );
+        let mut target = Box::new_in(
+            2_u32,
+            AllocatedBuffer::new_in(&&counting).unwrap(),
+            &counting,
+        );
+        let ptr: *const u32 = &*target;
+
+        target.clone_from(&source);
+
+        assert_eq!(*target, 1);
+        assert_eq!(ptr, &*target);
+        assert_eq!(counting.allocations.get(), 2);
+    }
+
+    #[test]
+    fn clone_from_slice() {
+        let counting = Counting::default();
+        let source = collect_in(0..4_u32, &counting);
+        let mut target = collect_in(4..8_u32, &counting);
+        let ptr: *const [u32] = &*target;
+
+        target.clone_from(&source);
+        assert_eq!(*target, [0, 1, 2, 3]);
+        assert_eq!(ptr, &*target);
+        assert_eq!(counting.allocations.get(), 2);
+
+        let source = collect_in(0..2_u32, &counting);
+        target.clone_from(&source);
+        assert_eq!(*target, [0, 1]);
+        assert_eq!(counting.allocations.get(), 4);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn ordering_across_buffers() {
+        let array: Box<[u32], [u32; 2], ()> = unsafe { Box::from_buffer([2, 0], ()) };
+        let mut heap: Vec<Box<[u32]>> = alloc::vec![
+            Box::from(&[3, 1][..]),
+            Box::from(&[1, 5][..]),
+            Box::from(&[2, 0][..]),
+        ];
+        heap.sort();
+
+        assert!(heap[0] < array);
+        assert!(heap[1] == array);
+        assert!(heap[2] > array);
+        assert_eq!(
+            heap.binary_search_by(|probe| probe.partial_cmp(&array).unwrap()),
+            Ok(1)
+        );
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn from_array() {
+        let value = Rc::new(());
+        let values = Box::<[Rc<()>]>::from([Rc::clone(&value), Rc::clone(&value)]);
+        assert_eq!(values.len(), 2);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn nested_box() {
+        fn read(value: &u32) -> u32 {
+            *value
+        }
+
+        let counting = Counting::default();
+        let inner = Box::new_in(
+            5_u32,
+            AllocatedBuffer::new_in(&&counting).unwrap(),
+            &counting,
+        );
+        let mut outer = Box::new_in(
+            inner,
+            AllocatedBuffer::new_in(&&counting).unwrap(),
+            &counting,
+        );
+
+        **outer += 1;
+        assert_eq!(read(&outer), 6);
+        assert_eq!(counting.allocations.get(), 2);
+
+        drop(outer);
+        assert_eq!(counting.deallocations.get(), 2);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn ptr_eq() {
+        let ptr: *mut u32 = Box::leak(Box::new(5_u32));
+        let buffer = || unsafe { AllocatedBuffer::from_raw(NonNull::new_unchecked(ptr)) };
+        let a = unsafe { Box::from_buffer(buffer(), Global) };
+        let b = mem::ManuallyDrop::new(unsafe { Box::from_buffer(buffer(), Global) });
+
+        assert!(Box::ptr_eq(&a, &b));
+        assert!(!Box::ptr_eq(&a, &Box::new(5)));
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "element 2 is not initialized")]
+    fn assume_init_checked_missing_element() {
+        let mut values = Box::<[u32]>::new_zeroed_slice(3);
+        values[0] = mem::MaybeUninit::new(1);
+        values[1] = mem::MaybeUninit::new(2);
+
+        unsafe { values.assume_init_checked(|value| value.as_ptr().read() != 0) };
+    }
+
+    #[test]
+    fn empty_slices_never_allocate() {
+        let counting = Counting::default();
+
+        let values = collect_in(core::iter::empty::<u32>(), &counting);
+        assert!(values.is_empty());
+        let (raw, allocator) = values.into_raw_box();
+        let values = raw.into_box(allocator);
+        assert_eq!(values.as_ptr() as usize % mem::align_of::<u32>(), 0);
+        drop(values);
+
+        let values = Box::<[u64], _>::try_from_slice_in(&[], &counting)
+            .ok()
+            .unwrap();
+        drop(values);
+
+        let mut values = Box::<[u32], _>::new_uninit_slice_in(
+            AllocatedBuffer::new_slice(&&counting, 0).unwrap(),
+            &counting,
+        );
+        values.try_reserve(2).unwrap();
+        assert!(values.len() >= 2);
+        assert_eq!(counting.allocations.get(), 1);
+        drop(values);
+
+        let values = collect_in(Some(1_u32).into_iter().filter(|_| false), &counting);
+        assert!(values.is_empty());
+
+        let value = Box::new_in((), AllocatedBuffer::new_in(&&counting).unwrap(), &counting);
+        drop(value);
+
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+        assert_eq!(
+            mem::size_of::<Option<Box<[u32]>>>(),
+            mem::size_of::<Box<[u32]>>()
+        );
+    }
+
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    fn drop_with_strategy<S: DropStrategy>() -> (usize, usize) {
+        let counting = Counting::default();
+        let drops = Cell::new(0);
+        let buffer = StrategyBuffer::<_, S>::new(AllocatedBuffer::new_in(&&counting).unwrap());
+        let boxed = Box::new_in(DropCounter(&drops), buffer, &counting);
+        let ptr = NonNull::from(&*boxed);
+
+        drop(boxed);
+        if !S::FREE {
+            unsafe { Global.deallocate(ptr.cast(), Layout::new::<DropCounter<'_>>()) };
+        }
+        (drops.get(), counting.deallocations.get())
+    }
+
+    #[test]
+    fn drop_strategies() {
+        use crate::buffer::{DropValueAndFree, DropValueOnly, FreeOnly, Neither};
+
+        assert_eq!(drop_with_strategy::<DropValueAndFree>(), (1, 1));
+        assert_eq!(drop_with_strategy::<DropValueOnly>(), (1, 0));
+        assert_eq!(drop_with_strategy::<FreeOnly>(), (0, 1));
+        assert_eq!(drop_with_strategy::<Neither>(), (0, 0));
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn byte_operations() {
+        let mut bytes = Box::<[u8]>::from([0; 6]);
+        Box::fill(&mut bytes, 0xAB);
+        assert_eq!(*bytes, [0xAB; 6]);
+
+        Box::copy_from(&mut bytes, &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(*bytes, [1, 2, 3, 4, 5, 6]);
+
+        let (chunks, remainder) = Box::as_chunks::<4>(&bytes);
+        assert_eq!(chunks, [[1, 2, 3, 4]]);
+        assert_eq!(remainder, [5, 6]);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[should_panic(expected = "source slice length does not match the boxed slice")]
+    fn copy_from_length_mismatch() {
+        let mut bytes = Box::<[u8]>::from([0; 4]);
+        Box::copy_from(&mut bytes, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn from_layout() {
+        let counting = Counting::default();
+        let layout = Layout::from_size_align(17, 8).unwrap();
+        let bytes = Box::from_layout(layout, &counting);
+
+        assert_eq!(bytes.len(), 17);
+        assert_eq!(bytes.as_ptr() as usize % 8, 0);
+        assert_eq!(bytes.raw.buffer().layout(), layout);
+
+        drop(bytes);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn hash_map_key() {
+        use alloc::string::String;
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+        map.insert(Box::new(String::from("one")), 1);
+        map.insert(Box::new(String::from("two")), 2);
+
+        assert_eq!(map.get(&String::from("one")), Some(&1));
+        assert_eq!(map.get(&Box::new(String::from("two"))), Some(&2));
+        assert_eq!(map.get(&String::from("three")), None);
+    }
+
+    #[test]
+    fn realloc_in() {
+        let source = Counting::default();
+        let target = Counting::default();
+
+        let values = Box::try_from_slice_in(&[1_u32, 2, 3, 4], &source).unwrap();
+        let values = values.realloc_in(&target);
+        assert_eq!(*values, [1, 2, 3, 4]);
+        assert_eq!(source.allocations.get(), 1);
+        assert_eq!(source.deallocations.get(), 1);
+
+        drop(values);
+        assert_eq!(target.allocations.get(), 1);
+        assert_eq!(target.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn realloc_in_drops_once() {
+        let value = Rc::new(());
+        let boxed = Box::new(Rc::clone(&value)).realloc_in(Global);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(boxed);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn split_off() {
+        let counting = Counting::default();
+        let value = Rc::new(());
+        let mut head =
+            Box::try_from_slice_in(&[Rc::clone(&value), Rc::clone(&value)], &counting).unwrap();
+        let tail = head.split_off(1);
+        assert_eq!(Rc::strong_count(&value), 3);
+        assert_eq!((head.len(), tail.len()), (1, 1));
+
+        drop(head);
+        drop(tail);
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(counting.allocations.get(), 2);
+        assert_eq!(counting.deallocations.get(), 2);
+
+        let mut head = Box::<[u32]>::from([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let tail = head.split_off(4);
+        assert_eq!(*head, [0, 1, 2, 3]);
+        assert_eq!(*tail, [4, 5, 6, 7, 8, 9]);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[should_panic(expected = "`at` split index (is 4) should be <= len (is 3)")]
+    fn split_off_out_of_bounds() {
+        Box::<[u32]>::from([1, 2, 3]).split_off(4);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn try_init_each() {
+        let value = Rc::new(());
+        let mut values = Box::<[Rc<()>]>::new_uninit_slice(5);
+        values
+            .try_init_each(|_| Ok::<_, ()>(Rc::clone(&value)))
+            .unwrap();
+        assert_eq!(Rc::strong_count(&value), 6);
+
+        drop(unsafe { values.assume_init() });
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn try_init_each_rolls_back() {
+        let value = Rc::new(());
+        let mut values = Box::<[Rc<()>]>::new_uninit_slice(5);
+        let result = values.try_init_each(|index| {
+            if index == 2 {
+                Err(index)
+            } else {
+                Ok(Rc::clone(&value))
+            }
+        });
+
+        assert_eq!(result, Err(2));
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn new_boxed_large() {
+        const LEN: usize = 1 << 20;
+
+        // the value would overflow the stack of the thread if it was constructed on it
+        let thread = std::thread::Builder::new().stack_size(LEN / 4).spawn(|| {
+            let bytes = unsafe {
+                Box::<[u8; LEN]>::new_boxed(|uninit| {
+                    let ptr = uninit.as_mut_ptr().cast::<u8>();
+                    for i in 0..LEN {
+                        ptr.add(i).write(i as u8);
+                    }
+                })
+            };
+            bytes.iter().enumerate().all(|(i, &byte)| byte == i as u8)
+        });
+
+        assert!(thread.unwrap().join().unwrap());
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn try_collect_box() {
+        let values = super::try_collect_box((0..4).map(Ok::<_, ()>)).unwrap();
+        assert_eq!(*values, [0, 1, 2, 3]);
+
+        let value = Rc::new(());
+        let result = super::try_collect_box((0..5).map(|index| {
+            if index == 3 {
+                Err(index)
+            } else {
+                Ok(Rc::clone(&value))
+            }
+        }));
+        assert!(result.err() == Some(3));
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn new_cache_aligned() {
+        let values = Box::<[u8]>::new_cache_aligned(3);
+        assert_eq!(*values, [0; 3]);
+        assert_eq!(values.as_ptr() as usize % 64, 0);
+
+        let empty = Box::<[u8]>::new_cache_aligned(0);
+        assert_eq!(empty.as_ptr() as usize % 64, 0);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn new_cache_aligned_across_threads() {
+        use std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc,
+            },
+            thread,
+        };
+
+        let counters = Arc::new(Box::<[AtomicUsize]>::new_cache_aligned(4));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let counters = Arc::clone(&counters);
+                thread::spawn(move || counters[i].fetch_add(i, Ordering::Relaxed))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let counters = Arc::try_unwrap(counters).ok().unwrap();
+        let counters = thread::spawn(move || counters).join().unwrap();
+        for (i, counter) in counters.iter().enumerate() {
+            assert_eq!(counter.load(Ordering::Relaxed), i);
+        }
+    }
+
+    #[test]
+    fn raw_parts_round_trip() {
+        let counting = Counting::default();
+        let values = Box::try_from_slice_in(&[1_u32, 2, 3], &counting).unwrap();
+        let (ptr, len, allocator) = values.into_raw_parts();
+        assert_eq!(len, 3);
+        assert_eq!(counting.deallocations.get(), 0);
+
+        let values = unsafe { Box::from_raw_parts(ptr, len, allocator) };
+        assert_eq!(*values, [1, 2, 3]);
+
+        drop(values);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn manually_drop_round_trip() {
+        let counting = Counting::default();
+        let value = Rc::new(());
+        let boxed = Box::try_new_in(Rc::clone(&value), &counting).ok().unwrap();
+
+        let boxed = boxed.into_manually_drop();
+        let boxed = Box::from_manually_drop(boxed);
+        drop(boxed);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let boxed = Box::try_new_in(Rc::clone(&value), &counting).ok().unwrap();
+        drop(boxed.into_manually_drop());
+        assert_eq!(Rc::strong_count(&value), 2);
+        assert_eq!(counting.allocations.get(), 2);
+        assert_eq!(counting.deallocations.get(), 2);
+    }
+
+    #[test]
+    fn slice_of_trait_objects() {
+        type Callback<'a> =
+            Box<dyn Fn() -> i32, AllocatedBuffer<dyn Fn() -> i32, &'a Counting>, &'a Counting>;
+
+        let counting = Counting::default();
+        let (one, two, three) = (1, 2, 3);
+        let callbacks: Vec<Callback<'_>> = alloc::vec![
+            Box::try_new_in(move || one, &counting).ok().unwrap(),
+            Box::try_new_in(move || two * 10, &counting).ok().unwrap(),
+            Box::try_new_in(move || -three, &counting).ok().unwrap(),
+        ];
+        let callbacks = collect_in(callbacks, &counting);
+        assert_eq!(callbacks.iter().map(|f| f()).sum::<i32>(), 1 + 20 - 3);
+        assert_eq!(counting.allocations.get(), 4);
+
+        drop(callbacks);
+        assert_eq!(counting.deallocations.get(), 4);
+    }
+
+    #[test]
+    fn boxed_str() {
+        let counting = Counting::default();
+        let bytes = Box::try_from_slice_in("grüße".as_bytes(), &counting).unwrap();
+        let text = Box::<str, _, _>::from_utf8(bytes).unwrap();
+        assert_eq!(text.len(), 7);
+        assert_eq!(text.chars().count(), 5);
+        assert_eq!(text.to_uppercase(), "GRÜSSE");
+        assert_eq!(text.find('ß'), Some(4));
+        assert_eq!(AsRef::<str>::as_ref(&text), "grüße");
+        assert_eq!(AsRef::<[u8]>::as_ref(&text), "grüße".as_bytes());
+
+        let bytes = text.into_boxed_bytes();
+        assert_eq!(bytes.len(), 7);
+        drop(bytes);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+
+        let invalid = Box::try_from_slice_in(&[b'a', 0xC3], &counting).unwrap();
+        assert!(Box::<str, _, _>::from_utf8(invalid).is_err());
+        assert_eq!(counting.deallocations.get(), 2);
+    }
+
+    #[test]
+    fn coerce_to_trait_object_in_allocator() {
+        trait Shape {
+            fn area(&self) -> u32;
+        }
+
+        struct Square(u32, Rc<()>);
+
+        impl Shape for Square {
+            fn area(&self) -> u32 {
+                self.0 * self.0
+            }
+        }
+
+        let recording = Recording::default();
+        let value = Rc::new(());
+        let square = Box::try_new_in(Square(3, Rc::clone(&value)), &recording)
+            .ok()
+            .unwrap();
+        let shape: Box<dyn Shape, AllocatedBuffer<dyn Shape, _>, _> = square;
+        assert!(ptr::eq(*shape.external_data(), &recording));
+        assert_eq!(shape.area(), 9);
+
+        drop(shape);
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(recording.allocated.get(), Some(Layout::new::<Square>()));
+        assert_eq!(recording.deallocated.get(), Some(Layout::new::<Square>()));
+    }
+
+    #[test]
+    fn assume_init_len() {
+        let counting = Counting::default();
+        let value = Rc::new(());
+        let buffer = AllocatedBuffer::new_slice_exact(&&counting, 8).unwrap();
+        let mut values = Box::<[Rc<()>], _>::new_uninit_slice_in(buffer, &counting);
+        for slot in &mut values[..3] {
+            *slot = mem::MaybeUninit::new(Rc::clone(&value));
+        }
+
+        let values = unsafe { values.assume_init_len(3) };
+        assert_eq!(values.len(), 3);
+        assert_eq!(counting.shrinks.get(), 1);
+
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    #[should_panic(expected = "`len` (is 3) should be <= the length of the boxed slice (is 2)")]
+    fn assume_init_len_out_of_bounds() {
+        let _ = unsafe { Box::<[u8]>::new_zeroed_slice(2).assume_init_len(3) };
+    }
+
+    #[test]
+    fn with_len() {
+        let counting = Counting::default();
+        let value = Rc::new(());
+        let buffer = TrackedBuffer::new_slice(&&counting, 4).unwrap();
+        let mut values = Box::<[Rc<()>], _>::new_uninit_slice_in(buffer, &counting);
+        for slot in values.iter_mut() {
+            *slot = mem::MaybeUninit::new(Rc::clone(&value));
+        }
+        let values = unsafe { values.assume_init() };
+        let tail = unsafe { (ptr::read(&values[2]), ptr::read(&values[3])) };
+
+        let values = unsafe { values.with_len(2) };
+        assert_eq!(values.iter().count(), 2);
+        assert_eq!(Rc::strong_count(&value), 5);
+
+        let mut values = unsafe { values.with_len(4) };
+        unsafe {
+            ptr::write(&mut values[2], tail.0);
+            ptr::write(&mut values[3], tail.1);
+        }
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.shrinks.get(), 0);
+        assert_eq!(counting.grows.get(), 0);
+
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(counting.bytes.get(), 0);
+    }
+
+    #[test]
+    fn with_len_excess_capacity() {
+        let rounding = Rounding::default();
+        let buffer = TrackedBuffer::<u32, _>::new_slice_zeroed(&&rounding, 3).unwrap();
+        let values = unsafe { Box::<[u32], _>::from_buffer(buffer, &rounding) };
+        assert_eq!(values.len(), 3);
+
+        let values = unsafe { values.with_len(16) };
+        assert_eq!(*values, [0; 16]);
+    }
+
+    #[test]
+    #[should_panic(expected = "`new_len` (is 3) should be <= capacity (is 2)")]
+    fn with_len_out_of_bounds() {
+        let buffer = TrackedBuffer::<u8, _>::new_slice_zeroed(&Global, 2).unwrap();
+        let _ = unsafe { Box::<[u8], _>::from_buffer(buffer, Global).with_len(3) };
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn as_array() {
+        let values = Box::<[u32]>::from([1, 2, 3]);
+        assert_eq!(values.as_array::<3>(), Some(&[1, 2, 3]));
+        assert_eq!(values.as_array::<2>(), None);
+        assert_eq!(values.as_array::<4>(), None);
+
+        let empty = Box::<[u32]>::from([]);
+        assert_eq!(empty.as_array::<0>(), Some(&[]));
     }
 }
@@ -1,13 +1,18 @@
 use core::{
-    alloc::Layout,
     marker::PhantomData,
     mem::{self},
     ops::CoerceUnsized,
 };
 
+#[cfg(feature = "global")]
 use alloc::alloc::{handle_alloc_error, Global};
+#[cfg(feature = "global")]
+use core::alloc::Layout;
 
-use crate::buffer::{AllocatedBuffer, Buffer, UnmanagedBuffer};
+use crate::{
+    boxed::Box,
+    buffer::{AllocatedBuffer, Buffer, ReadBuffer, UnmanagedBuffer},
+};
 
 /// A thin wrapper around a buffer.
 ///
@@ -67,13 +72,39 @@ use crate::buffer::{AllocatedBuffer, Buffer, UnmanagedBuffer};
 pub struct RawBox<T, B = AllocatedBuffer<T>>
 where
     T: ?Sized,
-    B: Buffer<T> + ?Sized,
+    B: ReadBuffer<T> + ?Sized,
 {
     _marker: PhantomData<fn() -> *const T>,
+    poison: Poison,
     buffer: B,
 }
 
+/// Tracks whether a raw box has been freed in place, if the `debug-checks` feature is enabled.
+#[derive(Default)]
+struct Poison {
+    #[cfg(feature = "debug-checks")]
+    freed: bool,
+}
+
+impl Poison {
+    #[allow(clippy::unused_self)]
+    fn set(&mut self) {
+        #[cfg(feature = "debug-checks")]
+        {
+            self.freed = true;
+        }
+    }
+
+    #[allow(clippy::unused_self)]
+    fn check(&self) {
+        #[cfg(feature = "debug-checks")]
+        debug_assert!(!self.freed, "the raw box has already been freed");
+    }
+}
+
 /// Construction of boxed values with a buffer backed by the global allocator.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
 #[allow(clippy::use_self)]
 impl<T> RawBox<T> {
     fn global_allocator_storage() -> AllocatedBuffer<T> {
@@ -163,6 +194,8 @@ impl<T> RawBox<T> {
 }
 
 /// Construction of boxed slices with a buffer backed by the global allocator.
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
 #[allow(clippy::use_self)]
 impl<T> RawBox<[T]> {
     /// Constructs a boxed slice with uninitialized contents.
@@ -193,9 +226,10 @@ impl<T> RawBox<[T]> {
     #[inline]
     pub fn new_uninit_slice(len: usize) -> RawBox<[mem::MaybeUninit<T>], AllocatedBuffer<[T]>> {
         RawBox {
-            buffer: AllocatedBuffer::new_slice(&Global, len)
+            buffer: AllocatedBuffer::new_slice_exact(&Global, len)
                 .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(len).unwrap())),
             _marker: PhantomData,
+            poison: Poison::default(),
         }
     }
 
@@ -223,9 +257,10 @@ impl<T> RawBox<[T]> {
     #[inline]
     pub fn new_zeroed_slice(len: usize) -> RawBox<[mem::MaybeUninit<T>], AllocatedBuffer<[T]>> {
         RawBox {
-            buffer: AllocatedBuffer::new_slice_zeroed(&Global, len)
+            buffer: AllocatedBuffer::new_slice_zeroed_exact(&Global, len)
                 .unwrap_or_else(|_| handle_alloc_error(Layout::array::<T>(len).unwrap())),
             _marker: PhantomData,
+            poison: Poison::default(),
         }
     }
 }
@@ -260,6 +295,7 @@ where
         Self {
             buffer,
             _marker: PhantomData,
+            poison: Poison::default(),
         }
     }
 
@@ -296,6 +332,7 @@ where
         RawBox {
             buffer,
             _marker: PhantomData,
+            poison: Poison::default(),
         }
     }
 }
@@ -336,13 +373,14 @@ where
         RawBox {
             buffer,
             _marker: PhantomData,
+            poison: Poison::default(),
         }
     }
 }
 
 impl<T: ?Sized, B> RawBox<T, B>
 where
-    B: Buffer<T>,
+    B: ReadBuffer<T>,
 {
     /// Creates a raw box from the provided buffer.
     ///
@@ -365,6 +403,7 @@ where
         Self {
             buffer,
             _marker: PhantomData,
+            poison: Poison::default(),
         }
     }
 }
@@ -389,6 +428,7 @@ where
     /// ```
     /// #![feature(allocator_api, new_uninit)]
     ///
+    /// # #[cfg(feature = "global")] {
     /// use std::alloc::Global;
     /// use storages::boxed::RawBox;
     ///
@@ -404,12 +444,15 @@ where
     /// assert_eq!(*five.as_ref(&Global), 5);
     ///
     /// five.free(&Global);
+    /// # }
     /// ```
     #[inline]
     pub unsafe fn assume_init(self) -> RawBox<T, B> {
+        self.poison.check();
         RawBox {
             buffer: self.buffer,
             _marker: PhantomData,
+            poison: self.poison,
         }
     }
 }
@@ -451,9 +494,11 @@ where
     /// ```
     #[inline]
     pub unsafe fn assume_init(self) -> RawBox<[T], B> {
+        self.poison.check();
         RawBox {
             buffer: self.buffer,
             _marker: PhantomData,
+            poison: self.poison,
         }
     }
 }
@@ -461,15 +506,83 @@ where
 impl<T, B> RawBox<T, B>
 where
     T: ?Sized,
-    B: Buffer<T>,
+    B: ReadBuffer<T>,
 {
+    /// Converts the raw box into a [`Box`], which stores `data` next to the buffer.
+    ///
+    /// [`Box`]: crate::boxed::Box
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::{boxed::RawBox, buffer::AllocatedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::new_in(&System)?;
+    /// let mut five = RawBox::<u32, _>::new_uninit_in(buffer);
+    ///
+    /// let five = unsafe {
+    ///     // Deferred initialization:
+    ///     five.as_mut(&System).as_mut_ptr().write(5);
+    ///
+    ///     five.assume_init()
+    /// };
+    /// let five = five.into_box(System);
+    ///
+    /// assert_eq!(*five, 5);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn into_box<D>(self, data: D) -> Box<T, B, D>
+    where
+        B: ReadBuffer<T, ExternalData = D>,
+    {
+        Box { raw: self, data }
+    }
+
     pub fn free(self, data: &B::ExternalData)
     where
         B: UnmanagedBuffer<T>,
     {
+        self.poison.check();
         self.buffer.free(data)
     }
 
+    /// Frees the buffer in place, e.g. when the raw box is stored in a collection, which is being
+    /// dropped.
+    ///
+    /// With the `debug-checks` feature enabled, accessing the box afterwards panics in debug
+    /// builds.
+    ///
+    /// # Safety
+    ///
+    /// The raw box must not be used after calling this method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::alloc::Global;
+    /// use storages::boxed::RawBox;
+    ///
+    /// let mut five = RawBox::new(5);
+    /// assert_eq!(*five.as_ref(&Global), 5);
+    ///
+    /// unsafe { five.free_unchecked(&Global) };
+    /// # }
+    /// ```
+    pub unsafe fn free_unchecked(&mut self, data: &B::ExternalData)
+    where
+        B: UnmanagedBuffer<T>,
+    {
+        self.poison.check();
+        self.buffer.free_unchecked(data);
+        self.poison.set();
+    }
+
     pub fn buffer(&self) -> &B {
         &self.buffer
     }
@@ -479,19 +592,121 @@ where
     }
 
     pub fn as_ref(&self, data: &B::ExternalData) -> &T {
+        self.poison.check();
         unsafe { &*self.buffer.as_ptr(data) }
     }
 
-    pub fn as_mut(&mut self, data: &B::ExternalData) -> &mut T {
+    pub fn as_mut(&mut self, data: &B::ExternalData) -> &mut T
+    where
+        B: Buffer<T>,
+    {
+        self.poison.check();
         unsafe { &mut *self.buffer.as_mut_ptr(data) }
     }
 }
 
+/// Shortcuts for buffers, which don't require external data.
+impl<T, B> RawBox<T, B>
+where
+    T: ?Sized,
+    B: ReadBuffer<T, ExternalData = ()>,
+{
+    /// Returns a reference to the boxed value, same as `as_ref(&())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::boxed::RawBox;
+    ///
+    /// let values = unsafe { RawBox::from_buffer([1_u32, 2, 3]) };
+    ///
+    /// assert_eq!(*values.as_ref_unit(), [1, 2, 3]);
+    /// ```
+    pub fn as_ref_unit(&self) -> &T {
+        self.as_ref(&())
+    }
+
+    /// Returns a mutable reference to the boxed value, same as `as_mut(&())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::boxed::RawBox;
+    ///
+    /// let mut values = unsafe { RawBox::from_buffer([1_u32, 2, 3]) };
+    /// values.as_mut_unit()[1] = 5;
+    ///
+    /// assert_eq!(*values.as_ref_unit(), [1, 5, 3]);
+    /// ```
+    pub fn as_mut_unit(&mut self) -> &mut T
+    where
+        B: Buffer<T>,
+    {
+        self.as_mut(&())
+    }
+
+    /// Frees the buffer, same as `free(&())`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use storages::{
+    ///     boxed::RawBox,
+    ///     buffer::{ReadBuffer, UnmanagedBuffer},
+    /// };
+    ///
+    /// struct Tracked<'a>(u32, &'a Cell<bool>);
+    ///
+    /// impl ReadBuffer<u32> for Tracked<'_> {
+    ///     type ExternalData = ();
+    ///
+    ///     const IS_MANAGED: bool = false;
+    ///
+    ///     fn as_ptr(&self, _data: &()) -> *const u32 {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// impl UnmanagedBuffer<u32> for Tracked<'_> {
+    ///     unsafe fn free_unchecked(&mut self, _data: &()) {
+    ///         self.1.set(true);
+    ///     }
+    /// }
+    ///
+    /// let freed = Cell::new(false);
+    /// let value = unsafe { RawBox::from_buffer(Tracked(5, &freed)) };
+    /// assert_eq!(*value.as_ref_unit(), 5);
+    ///
+    /// value.free_unit();
+    /// assert!(freed.get());
+    /// ```
+    pub fn free_unit(self)
+    where
+        B: UnmanagedBuffer<T>,
+    {
+        self.free(&())
+    }
+}
+
 impl<T, U, BT, BU> CoerceUnsized<RawBox<U, BU>> for RawBox<T, BT>
 where
     T: ?Sized,
     U: ?Sized,
-    BT: Buffer<T> + CoerceUnsized<BU>,
-    BU: Buffer<U>,
+    BT: ReadBuffer<T> + CoerceUnsized<BU>,
+    BU: ReadBuffer<U>,
 {
 }
+
+#[cfg(all(test, feature = "debug-checks"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "the raw box has already been freed")]
+    fn use_after_free() {
+        let mut five = RawBox::new(5);
+        unsafe { five.free_unchecked(&Global) };
+        five.as_ref(&Global);
+    }
+}
@@ -0,0 +1,120 @@
+use super::{dangling, Buffer, ReadBuffer, UnmanagedBuffer};
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    marker::PhantomData,
+    mem,
+    ptr::NonNull,
+};
+
+/// A slice buffer, which is aligned to at least `ALIGN` bytes.
+///
+/// This is useful to place the elements on their own cache line to avoid false sharing between
+/// threads, see [`Box::new_cache_aligned`]. The alignment is part of the type, so the buffer is
+/// freed with the layout it was allocated with.
+///
+/// [`Box::new_cache_aligned`]: crate::boxed::Box::new_cache_aligned
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use storages::buffer::{AlignedBuffer, ReadBuffer, UnmanagedBuffer};
+///
+/// let buffer = AlignedBuffer::<u8, _, 128>::new_slice_in(&Global, 3)?;
+/// let bytes: *const [u8] = buffer.as_ptr(&Global);
+///
+/// assert_eq!(bytes as *const u8 as usize % 128, 0);
+///
+/// UnmanagedBuffer::<[u8]>::free(buffer, &Global);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub struct AlignedBuffer<T, A: ?Sized, const ALIGN: usize> {
+    ptr: NonNull<[T]>,
+    _owned: PhantomData<T>,
+    _marker: PhantomData<fn(*const A)>,
+}
+
+unsafe impl<T: Send, A: ?Sized + Send, const ALIGN: usize> Send for AlignedBuffer<T, A, ALIGN> {}
+unsafe impl<T: Sync, A: ?Sized + Sync, const ALIGN: usize> Sync for AlignedBuffer<T, A, ALIGN> {}
+
+impl<T, A: ?Sized, const ALIGN: usize> AlignedBuffer<T, A, ALIGN> {
+    fn layout(len: usize) -> Result<Layout, AllocError> {
+        Layout::array::<T>(len)
+            .and_then(|layout| layout.align_to(ALIGN))
+            .map_err(|_| AllocError)
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> AlignedBuffer<T, A, ALIGN> {
+    /// Allocates an aligned buffer for `len` elements in the provided allocator.
+    ///
+    /// Returns an error if `ALIGN` is not a power of two or if the allocation fails. Empty
+    /// buffers don't allocate.
+    pub fn new_slice_in(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        let layout = Self::layout(len)?;
+        let ptr = if layout.size() == 0 {
+            dangling(layout).cast()
+        } else {
+            allocator.allocate(layout)?.cast()
+        };
+        Ok(Self {
+            ptr: NonNull::slice_from_raw_parts(ptr, len),
+            _owned: PhantomData,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> ReadBuffer<[T]> for AlignedBuffer<T, A, ALIGN> {
+    type ExternalData = A;
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [T] {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> Buffer<[T]> for AlignedBuffer<T, A, ALIGN> {
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [T] {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> ReadBuffer<[mem::MaybeUninit<T>]>
+    for AlignedBuffer<T, A, ALIGN>
+{
+    type ExternalData = A;
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [mem::MaybeUninit<T>] {
+        self.ptr.as_ptr() as *const [mem::MaybeUninit<T>]
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> Buffer<[mem::MaybeUninit<T>]>
+    for AlignedBuffer<T, A, ALIGN>
+{
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [mem::MaybeUninit<T>] {
+        self.ptr.as_ptr() as *mut [mem::MaybeUninit<T>]
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> UnmanagedBuffer<[T]>
+    for AlignedBuffer<T, A, ALIGN>
+{
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        // the layout was valid when allocating, so it's valid for the same length
+        let layout = Self::layout(self.ptr.len()).unwrap_or_else(|_| unreachable!());
+        if layout.size() != 0 {
+            allocator.deallocate(self.ptr.cast(), layout);
+        }
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const ALIGN: usize> UnmanagedBuffer<[mem::MaybeUninit<T>]>
+    for AlignedBuffer<T, A, ALIGN>
+{
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        UnmanagedBuffer::<[T]>::free_unchecked(self, allocator)
+    }
+}
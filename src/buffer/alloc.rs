@@ -1,14 +1,21 @@
-use super::{Buffer, UnmanagedBuffer};
-use alloc::alloc::Global;
+use super::{AllocateBuffer, Buffer, GrowableBuffer, ReadBuffer, UnmanagedBuffer};
+use alloc::{alloc::Global, collections::TryReserveError};
 use core::{
     alloc::{AllocError, Allocator, Layout},
+    cmp,
     marker::{PhantomData, Unsize},
     mem,
     ops::CoerceUnsized,
-    ptr::NonNull,
+    ptr::{self, NonNull},
     slice,
 };
 
+/// An allocator, which never fails to allocate memory.
+///
+/// Implementing this trait allows constructing collections in the allocator without handling
+/// [`AllocError`]. If the allocator fails anyway, the allocation error handler is called.
+pub trait InfallibleAllocator: Allocator {}
+
 #[derive(Copy, Clone)]
 enum Init {
     Unspecified,
@@ -29,8 +36,81 @@ impl<T: ?Sized, A: ?Sized> AllocatedBuffer<T, A> {
             _marker: PhantomData,
         }
     }
+
+    /// Consumes the buffer and returns the wrapped pointer.
+    ///
+    /// The memory is not freed, so it's up to the caller to free it, e.g. by passing the pointer
+    /// to [`from_raw`] again.
+    ///
+    /// [`from_raw`]: Self::from_raw
+    pub fn into_raw(self) -> NonNull<T> {
+        self.ptr
+    }
+
+    /// Returns `true` if both buffers point to the same memory.
+    ///
+    /// Only the addresses are compared, metadata like the length of a slice is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::alloc::Global;
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let a = AllocatedBuffer::<u32>::new()?;
+    /// let b = AllocatedBuffer::<u32>::new()?;
+    /// assert!(a.ptr_eq(&a));
+    /// assert!(!a.ptr_eq(&b));
+    ///
+    /// a.free(&Global);
+    /// b.free(&Global);
+    /// # }
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.ptr.cast::<u8>() == other.ptr.cast::<u8>()
+    }
+
+    /// Creates a second handle to the same allocation without copying the data.
+    ///
+    /// This is intended for building custom shared structures on top of a buffer, e.g. with an
+    /// external reference count.
+    ///
+    /// # Safety
+    ///
+    /// Both handles point to the same memory, so only one of them may be freed, and neither may
+    /// be used after that. Freeing both results in a double free. The caller must also ensure,
+    /// that mutable access through one handle does not alias any other access.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::alloc::Global;
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::<u32>::new()?;
+    /// let alias = unsafe { buffer.alias() };
+    /// assert!(buffer.ptr_eq(&alias));
+    ///
+    /// // only one of the handles is freed
+    /// drop(alias);
+    /// buffer.free(&Global);
+    /// # }
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub unsafe fn alias(&self) -> Self {
+        Self::from_raw(self.ptr)
+    }
 }
 
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
 impl<T> AllocatedBuffer<T> {
     pub fn new() -> Result<Self, AllocError> {
         Self::new_in(&Global)
@@ -44,6 +124,9 @@ impl<T> AllocatedBuffer<T> {
 impl<T, A: ?Sized + Allocator> AllocatedBuffer<T, A> {
     fn allocate_in(allocator: &A, init: Init) -> Result<Self, AllocError> {
         let layout = Layout::new::<T>();
+        if layout.size() == 0 {
+            return unsafe { Ok(Self::from_raw(NonNull::dangling())) };
+        }
         let ptr = match init {
             Init::Unspecified => allocator.allocate(layout)?,
             Init::Zeroed => allocator.allocate_zeroed(layout)?,
@@ -60,15 +143,107 @@ impl<T, A: ?Sized + Allocator> AllocatedBuffer<T, A> {
     }
 }
 
+impl<T, A: ?Sized> AllocatedBuffer<[T], A> {
+    /// Creates a slice buffer from a pointer to an allocation of `capacity` elements.
+    ///
+    /// This is the inverse of [`into_raw`] for slice buffers, e.g. to take over an allocation
+    /// made by another collection.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a block of memory, which was allocated by the allocator passed to the
+    /// buffer later on, with the layout of `[T; capacity]`. For zero-sized types, `ptr` may be
+    /// dangling.
+    ///
+    /// [`into_raw`]: Self::into_raw
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::{alloc::Global, mem::ManuallyDrop, ptr::NonNull};
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let mut values = ManuallyDrop::new(vec![1_u32, 2, 3]);
+    /// let capacity = values.capacity();
+    /// let ptr = NonNull::new(values.as_mut_ptr()).unwrap();
+    ///
+    /// let buffer = unsafe { AllocatedBuffer::<[u32]>::from_raw_parts(ptr, capacity) };
+    ///
+    /// assert_eq!(buffer.capacity(), capacity);
+    ///
+    /// buffer.free(&Global);
+    /// ```
+    pub unsafe fn from_raw_parts(ptr: NonNull<T>, capacity: usize) -> Self {
+        Self::from_raw(NonNull::slice_from_raw_parts(ptr, capacity))
+    }
+}
+
+impl<T: Copy, A: ?Sized> AllocatedBuffer<[T], A> {
+    /// Copies the first `count` elements of `src` to the start of this buffer.
+    ///
+    /// The elements are copied bytewise, so uninitialized elements stay uninitialized. Both
+    /// buffers may share their memory, e.g. when one was created by [`alias`], in which case the
+    /// copy behaves as if the elements were copied to a temporary first.
+    ///
+    /// [`alias`]: Self::alias
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` exceeds the capacity of either buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::alloc::Global;
+    /// use storages::{boxed::Box, buffer::AllocatedBuffer};
+    ///
+    /// let src = Box::<[u32]>::from([1, 2, 3]);
+    /// let (src, _) = src.into_raw_box();
+    /// let mut dst = AllocatedBuffer::<[u32], _>::new_slice_zeroed(&Global, 4)?;
+    /// dst.copy_within_from(src.buffer(), 2);
+    ///
+    /// let dst = unsafe { Box::<[u32], _>::from_buffer(dst, Global) };
+    /// assert_eq!(*dst, [1, 2, 0, 0]);
+    /// src.free(&Global);
+    /// # }
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn copy_within_from(&mut self, src: &Self, count: usize) {
+        assert!(
+            count <= src.ptr.len() && count <= self.ptr.len(),
+            "cannot copy {} elements from a buffer with a capacity of {} into a buffer with a \
+             capacity of {}",
+            count,
+            src.ptr.len(),
+            self.ptr.len()
+        );
+        unsafe { ptr::copy(src.ptr.as_mut_ptr(), self.ptr.as_mut_ptr(), count) }
+    }
+}
+
 impl<T, A: ?Sized + Allocator> AllocatedBuffer<[T], A> {
-    fn capacity_from_bytes(bytes: usize) -> usize {
+    fn capacity_from_bytes(bytes: usize, requested: usize) -> usize {
         debug_assert_ne!(mem::size_of::<T>(), 0);
-        bytes / mem::size_of::<T>()
+        let capacity = bytes / mem::size_of::<T>();
+        debug_assert!(
+            capacity >= requested,
+            "the allocator returned less memory than requested"
+        );
+        capacity
     }
 
     #[allow(clippy::map_err_ignore)]
     fn allocate_slice(allocator: &A, len: usize, init: Init) -> Result<Self, AllocError> {
         let ptr = if mem::size_of::<T>() == 0 {
+            // zero-sized elements never allocate but still have a capacity of `len`
+            NonNull::slice_from_raw_parts(NonNull::dangling(), len)
+        } else if len == 0 {
+            // empty buffers never allocate
             NonNull::slice_from_raw_parts(NonNull::dangling(), 0)
         } else {
             let layout = Layout::array::<T>(len).map_err(|_| AllocError)?;
@@ -80,66 +255,407 @@ impl<T, A: ?Sized + Allocator> AllocatedBuffer<[T], A> {
 
             NonNull::slice_from_raw_parts(
                 ptr.as_non_null_ptr().cast(),
-                Self::capacity_from_bytes(ptr.len()),
+                Self::capacity_from_bytes(ptr.len(), len),
             )
         };
         unsafe { Ok(Self::from_raw(ptr)) }
     }
 
+    /// Allocates a buffer for at least `len` elements.
+    ///
+    /// If the allocator returns a larger block than requested, the excess memory is used, so the
+    /// [`capacity`] may exceed `len`.
+    ///
+    /// [`capacity`]: Self::capacity
     pub fn new_slice(allocator: &A, len: usize) -> Result<Self, AllocError> {
         Self::allocate_slice(allocator, len, Init::Unspecified)
     }
 
+    /// Allocates a buffer for at least `len` elements with the memory being filled with `0`
+    /// bytes.
+    ///
+    /// If the allocator returns a larger block than requested, the excess memory is used, so the
+    /// [`capacity`] may exceed `len`.
+    ///
+    /// [`capacity`]: Self::capacity
     pub fn new_slice_zeroed(allocator: &A, len: usize) -> Result<Self, AllocError> {
         Self::allocate_slice(allocator, len, Init::Zeroed)
     }
+
+    /// Allocates a buffer for exactly `len` elements.
+    ///
+    /// Unlike [`new_slice`], excess memory returned by the allocator is not kept: if the returned
+    /// block is larger than requested, it's shrunk to `len` elements, so the [`capacity`] equals
+    /// `len`. This costs an additional call to [`Allocator::shrink`], which may reallocate and
+    /// copy, when the allocator can't shrink in place. If shrinking fails, the larger block is
+    /// kept but only `len` elements are exposed.
+    ///
+    /// [`new_slice`]: Self::new_slice
+    /// [`capacity`]: Self::capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::<[u32], _>::new_slice_exact(&System, 3)?;
+    ///
+    /// assert_eq!(buffer.capacity(), 3);
+    ///
+    /// buffer.free(&System);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn new_slice_exact(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        Self::allocate_slice(allocator, len, Init::Unspecified)
+            .map(|buffer| buffer.exact(allocator, len))
+    }
+
+    /// Allocates a buffer for exactly `len` elements with the memory being filled with `0` bytes.
+    ///
+    /// See [`new_slice_exact`] for how this differs from [`new_slice_zeroed`].
+    ///
+    /// [`new_slice_exact`]: Self::new_slice_exact
+    /// [`new_slice_zeroed`]: Self::new_slice_zeroed
+    pub fn new_slice_zeroed_exact(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        Self::allocate_slice(allocator, len, Init::Zeroed)
+            .map(|buffer| buffer.exact(allocator, len))
+    }
+
+    /// Shrinks the buffer to `len` elements.
+    ///
+    /// If the allocator fails to shrink the block, only the view is restricted. Memory can be
+    /// deallocated or grown with any layout between the requested and the returned size, so a
+    /// view of the requested `len` elements stays valid.
+    fn exact(self, allocator: &A, len: usize) -> Self {
+        let capacity = self.ptr.len();
+        if mem::size_of::<T>() == 0 || capacity == len {
+            return unsafe { Self::from_raw_parts(self.ptr.as_non_null_ptr(), len) };
+        }
+        debug_assert!(len < capacity);
+
+        unsafe {
+            let align = mem::align_of::<T>();
+            let old_layout =
+                Layout::from_size_align_unchecked(capacity * mem::size_of::<T>(), align);
+            let new_layout = Layout::from_size_align_unchecked(len * mem::size_of::<T>(), align);
+            let ptr = self.ptr.as_non_null_ptr();
+            match allocator.shrink(ptr.cast(), old_layout, new_layout) {
+                Ok(shrunk) => Self::from_raw_parts(shrunk.as_non_null_ptr().cast(), len),
+                Err(_) => Self::from_raw_parts(ptr, len),
+            }
+        }
+    }
+
+    /// Returns the number of elements the buffer can hold.
+    ///
+    /// This is at least the requested length, but may be larger if the allocator returned a
+    /// larger block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let buffer = AllocatedBuffer::<[u32], _>::new_slice(&System, 3)?;
+    ///
+    /// assert!(buffer.capacity() >= 3);
+    ///
+    /// buffer.free(&System);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.ptr.len()
+    }
+
+    /// Ensures that the buffer can hold at least `len + additional` elements.
+    ///
+    /// If the capacity is not sufficient, it is at least doubled to amortize consecutive
+    /// reservations. The elements in the grown part are uninitialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let mut buffer = AllocatedBuffer::<[u32], _>::new_slice(&System, 3)?;
+    /// buffer.try_reserve(&System, 3, 2)?;
+    ///
+    /// assert!(buffer.capacity() >= 5);
+    ///
+    /// buffer.free(&System);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[allow(clippy::map_err_ignore)]
+    pub fn try_reserve(
+        &mut self,
+        allocator: &A,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let capacity = self.ptr.len();
+        let required = len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if required <= capacity {
+            return Ok(());
+        }
+        if mem::size_of::<T>() == 0 {
+            self.ptr = NonNull::slice_from_raw_parts(NonNull::dangling(), required);
+            return Ok(());
+        }
+
+        let new_capacity = checked_grow_capacity(capacity, required)?;
+        let new_layout =
+            Layout::array::<T>(new_capacity).map_err(|_| TryReserveError::CapacityOverflow)?;
+        alloc_guard(new_layout.size()).map_err(|_| TryReserveError::CapacityOverflow)?;
+        let ptr = if capacity == 0 {
+            allocator.allocate(new_layout)
+        } else {
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(
+                    mem::size_of::<T>() * capacity,
+                    mem::align_of::<T>(),
+                );
+                allocator.grow(self.ptr.cast(), old_layout, new_layout)
+            }
+        }
+        .map_err(|_| TryReserveError::AllocError {
+            layout: new_layout,
+            non_exhaustive: (),
+        })?;
+
+        self.ptr = NonNull::slice_from_raw_parts(
+            ptr.as_non_null_ptr().cast(),
+            Self::capacity_from_bytes(ptr.len(), new_capacity),
+        );
+        Ok(())
+    }
+
+    /// Grows the buffer to a capacity of at least `len` elements with the new memory being
+    /// filled with `0` bytes.
+    ///
+    /// The existing elements are kept. If the capacity is already greater than or equal to `len`,
+    /// this is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::{boxed::Box, buffer::AllocatedBuffer};
+    ///
+    /// let mut buffer = AllocatedBuffer::<[u8], _>::new_slice_zeroed(&System, 4)?;
+    /// buffer.grow_zeroed(&System, 8)?;
+    ///
+    /// let bytes = unsafe { Box::<[u8], _>::from_buffer(buffer, System) };
+    /// assert!(bytes.len() >= 8);
+    /// assert!(bytes.iter().all(|&byte| byte == 0));
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    #[allow(clippy::map_err_ignore)]
+    pub fn grow_zeroed(&mut self, allocator: &A, len: usize) -> Result<(), AllocError> {
+        let capacity = self.ptr.len();
+        if len <= capacity {
+            return Ok(());
+        }
+        if mem::size_of::<T>() == 0 {
+            self.ptr = NonNull::slice_from_raw_parts(self.ptr.as_non_null_ptr(), len);
+            return Ok(());
+        }
+
+        let new_layout = Layout::array::<T>(len).map_err(|_| AllocError)?;
+        alloc_guard(new_layout.size()).map_err(|_| AllocError)?;
+        let ptr = if capacity == 0 {
+            allocator.allocate_zeroed(new_layout)?
+        } else {
+            unsafe {
+                let old_layout = Layout::from_size_align_unchecked(
+                    mem::size_of::<T>() * capacity,
+                    mem::align_of::<T>(),
+                );
+                allocator.grow_zeroed(self.ptr.cast(), old_layout, new_layout)?
+            }
+        };
+        self.ptr = NonNull::slice_from_raw_parts(
+            ptr.as_non_null_ptr().cast(),
+            Self::capacity_from_bytes(ptr.len(), len),
+        );
+        Ok(())
+    }
+
+    /// Shrinks the buffer to a capacity of `len` elements.
+    ///
+    /// The capacity is exactly `len` afterwards, even if the allocator returned a larger block.
+    /// If the capacity is already less than or equal to `len`, this is a no-op. Elements beyond
+    /// `len` are not dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    /// use storages::buffer::{AllocatedBuffer, UnmanagedBuffer};
+    ///
+    /// let mut buffer = AllocatedBuffer::<[u32], _>::new_slice(&System, 8)?;
+    /// buffer.shrink(&System, 3)?;
+    ///
+    /// assert_eq!(buffer.capacity(), 3);
+    ///
+    /// buffer.free(&System);
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    pub fn shrink(&mut self, allocator: &A, len: usize) -> Result<(), AllocError> {
+        let capacity = self.ptr.len();
+        if len >= capacity {
+            return Ok(());
+        }
+        if mem::size_of::<T>() != 0 {
+            let old_layout = unsafe {
+                Layout::from_size_align_unchecked(
+                    mem::size_of::<T>() * capacity,
+                    mem::align_of::<T>(),
+                )
+            };
+            if len == 0 {
+                // empty buffers don't hold an allocation
+                unsafe { allocator.deallocate(self.ptr.cast(), old_layout) };
+                self.ptr = NonNull::slice_from_raw_parts(NonNull::dangling(), 0);
+                return Ok(());
+            }
+            let ptr = unsafe {
+                let new_layout = Layout::from_size_align_unchecked(
+                    mem::size_of::<T>() * len,
+                    mem::align_of::<T>(),
+                );
+                allocator.shrink(self.ptr.cast(), old_layout, new_layout)?
+            };
+            self.ptr = NonNull::slice_from_raw_parts(ptr.as_non_null_ptr().cast(), len);
+        } else {
+            self.ptr = NonNull::slice_from_raw_parts(self.ptr.as_non_null_ptr(), len);
+        }
+        Ok(())
+    }
 }
 
-impl<T: ?Sized, A: ?Sized + Allocator> Buffer<T> for AllocatedBuffer<T, A> {
+impl<T: ?Sized, A: ?Sized + Allocator> ReadBuffer<T> for AllocatedBuffer<T, A> {
     type ExternalData = A;
 
     fn as_ptr(&self, _data: &Self::ExternalData) -> *const T {
         self.ptr.as_ptr()
     }
+}
 
+impl<T: ?Sized, A: ?Sized + Allocator> Buffer<T> for AllocatedBuffer<T, A> {
     fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut T {
         self.ptr.as_ptr()
     }
 }
 
-impl<T, A: ?Sized + Allocator> Buffer<mem::MaybeUninit<T>> for AllocatedBuffer<T, A> {
+impl<T, A: ?Sized + Allocator> ReadBuffer<mem::MaybeUninit<T>> for AllocatedBuffer<T, A> {
     type ExternalData = A;
 
+    // only the initialized buffer is an `UnmanagedBuffer`, so the default can't be derived
+    const IS_MANAGED: bool = false;
+
     fn as_ptr(&self, _data: &Self::ExternalData) -> *const mem::MaybeUninit<T> {
         self.ptr.as_ptr().cast()
     }
+}
 
+impl<T, A: ?Sized + Allocator> Buffer<mem::MaybeUninit<T>> for AllocatedBuffer<T, A> {
     fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut mem::MaybeUninit<T> {
         self.ptr.as_ptr().cast()
     }
 }
 
-impl<T, A: ?Sized + Allocator> Buffer<[mem::MaybeUninit<T>]> for AllocatedBuffer<[T], A> {
+impl<T, A: ?Sized + Allocator> ReadBuffer<[mem::MaybeUninit<T>]> for AllocatedBuffer<[T], A> {
     type ExternalData = A;
 
+    // only the initialized buffer is an `UnmanagedBuffer`, so the default can't be derived
+    const IS_MANAGED: bool = false;
+
     fn as_ptr(&self, _data: &Self::ExternalData) -> *const [mem::MaybeUninit<T>] {
         unsafe { slice::from_raw_parts(self.ptr.cast().as_ptr(), self.ptr.len()) }
     }
+}
 
+impl<T, A: ?Sized + Allocator> Buffer<[mem::MaybeUninit<T>]> for AllocatedBuffer<[T], A> {
     fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [mem::MaybeUninit<T>] {
         unsafe { slice::from_raw_parts_mut(self.ptr.cast().as_ptr(), self.ptr.len()) }
     }
 }
 
-impl<T: ?Sized, A: Allocator> UnmanagedBuffer<T> for AllocatedBuffer<T, A> {
+/// Allocates a buffer for at least `len` elements, see [`new_slice`].
+///
+/// [`new_slice`]: AllocatedBuffer::new_slice
+impl<T, A: Allocator> AllocateBuffer<[mem::MaybeUninit<T>]> for AllocatedBuffer<[T], A> {
+    fn allocate(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        Self::new_slice(allocator, len)
+    }
+}
+
+impl<T, A: Allocator> GrowableBuffer<[mem::MaybeUninit<T>]> for AllocatedBuffer<[T], A> {
+    fn try_reserve(
+        &mut self,
+        allocator: &Self::ExternalData,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        Self::try_reserve(self, allocator, len, additional)
+    }
+}
+
+impl<T: ?Sized, A: ?Sized + Allocator> UnmanagedBuffer<T> for AllocatedBuffer<T, A> {
     unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
         let size = mem::size_of_val(self.ptr.as_ref());
         let align = mem::align_of_val(self.ptr.as_ref());
+        if size == 0 {
+            // zero-sized buffers are dangling
+            return;
+        }
         let layout = Layout::from_size_align_unchecked(size, align);
         allocator.deallocate(self.ptr.cast(), layout);
     }
 }
 
+/// Computes the capacity for growing a buffer from `current` to at least `required` elements.
+///
+/// The capacity is at least doubled to amortize the growth. Capacities exceeding `isize::MAX`
+/// are reported as [`TryReserveError::CapacityOverflow`].
+pub(crate) fn checked_grow_capacity(
+    current: usize,
+    required: usize,
+) -> Result<usize, TryReserveError> {
+    let doubled = current
+        .checked_mul(2)
+        .ok_or(TryReserveError::CapacityOverflow)?;
+    let capacity = cmp::max(doubled, required);
+    if capacity > isize::MAX as usize {
+        Err(TryReserveError::CapacityOverflow)
+    } else {
+        Ok(capacity)
+    }
+}
+
+/// Returns a dangling pointer, which is well-aligned for `layout`.
+///
+/// This is used in place of an allocation for zero-sized layouts.
+pub(crate) fn dangling(layout: Layout) -> NonNull<u8> {
+    // `layout.align()` is non-zero, so this is a well-aligned dangling pointer
+    unsafe { NonNull::new_unchecked(layout.align() as *mut u8) }
+}
+
 #[inline]
 const fn alloc_guard(alloc_size: usize) -> Result<(), AllocError> {
     if usize::BITS < 64 && alloc_size > isize::MAX as usize {
@@ -153,3 +669,293 @@ impl<T: ?Sized + Unsize<U>, U: ?Sized, A: Allocator> CoerceUnsized<AllocatedBuff
     for AllocatedBuffer<T, A>
 {
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        boxed::Box,
+        buffer::{
+            AlignedBuffer,
+            InlineBuffer,
+            LayoutBuffer,
+            OnceBuffer,
+            SlabBuffer,
+            SmallBuffer,
+            StatsBuffer,
+            TrackedBuffer,
+        },
+        testing::{Counting, Rounding},
+    };
+
+    /// Allocates one byte more than requested, or reports one byte less if `short` is set.
+    struct Odd {
+        short: bool,
+    }
+
+    unsafe impl Allocator for Odd {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let size = layout.size() + 1;
+            let layout = Layout::from_size_align(size, layout.align()).map_err(|_| AllocError)?;
+            let ptr = Global.allocate(layout)?;
+            let len = if self.short { size - 2 } else { size };
+            Ok(NonNull::slice_from_raw_parts(ptr.as_non_null_ptr(), len))
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            let size = layout.size() + 1;
+            Global.deallocate(ptr, Layout::from_size_align_unchecked(size, layout.align()))
+        }
+    }
+
+    #[test]
+    fn from_raw_parts() {
+        let counting = Counting::default();
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice_zeroed(&&counting, 4).unwrap();
+        let ptr = buffer.into_raw();
+
+        let buffer = unsafe {
+            AllocatedBuffer::<[u32], &Counting>::from_raw_parts(ptr.as_non_null_ptr(), ptr.len())
+        };
+        let values = unsafe { Box::<[u32], _>::from_buffer(buffer, &counting) };
+        assert_eq!(*values, [0; 4]);
+
+        drop(values);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn excess_capacity() {
+        let rounding = Rounding::default();
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice(&rounding, 3).unwrap();
+
+        assert_eq!(buffer.capacity(), 16);
+
+        buffer.free(&rounding);
+    }
+
+    #[test]
+    fn buffer_size() {
+        assert_eq!(
+            mem::size_of::<AllocatedBuffer<u32>>(),
+            mem::size_of::<usize>()
+        );
+        // `debug-checks` adds a poison flag to boxes
+        #[cfg(not(feature = "debug-checks"))]
+        assert_eq!(mem::size_of::<Box<u32>>(), mem::size_of::<usize>());
+        assert_eq!(
+            mem::size_of::<AllocatedBuffer<[u32]>>(),
+            mem::size_of::<&[u32]>()
+        );
+    }
+
+    #[test]
+    fn zero_sized_capacity() {
+        let buffer = AllocatedBuffer::<[()]>::allocate(&Global, 5).unwrap();
+        assert_eq!(buffer.capacity(), 5);
+        buffer.free(&Global);
+
+        let buffer = AllocatedBuffer::<[()], _>::new_slice_exact(&Global, 5).unwrap();
+        assert_eq!(buffer.capacity(), 5);
+        buffer.free(&Global);
+    }
+
+    #[test]
+    fn exact_slice_with_excess_capacity() {
+        let rounding = Rounding::default();
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice_exact(&&rounding, 3).unwrap();
+        assert_eq!(buffer.capacity(), 3);
+
+        let mut values = Box::<[u32], _>::new_uninit_slice_in(buffer, &rounding);
+        assert_eq!(values.len(), 3);
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = mem::MaybeUninit::new(i as u32);
+        }
+        let values = unsafe { values.assume_init() };
+
+        assert_eq!(*values, [0, 1, 2]);
+    }
+
+    fn is_managed<T: ?Sized, B: ReadBuffer<T>>() -> bool {
+        B::IS_MANAGED
+    }
+
+    #[test]
+    fn managed_buffers() {
+        assert!(!is_managed::<u32, AllocatedBuffer<u32>>());
+        assert!(!is_managed::<[mem::MaybeUninit<u32>], AllocatedBuffer<[u32]>>());
+        assert!(!is_managed::<u32, StatsBuffer<AllocatedBuffer<u32>>>());
+        assert!(is_managed::<[u32], [u32; 2]>());
+        assert!(is_managed::<[u32], [mem::MaybeUninit<u32>; 2]>());
+        assert!(is_managed::<[u32], InlineBuffer<u32, 2>>());
+        assert!(!is_managed::<u32, SlabBuffer<'_, u32>>());
+        assert!(!is_managed::<mem::MaybeUninit<u32>, AllocatedBuffer<u32>>());
+        assert!(!is_managed::<[u32], SmallBuffer<u32, Global, 2>>());
+        assert!(!is_managed::<
+            [mem::MaybeUninit<u32>],
+            SmallBuffer<u32, Global, 2>,
+        >());
+        assert!(!is_managed::<[u32], AlignedBuffer<u32, Global, 64>>());
+        assert!(!is_managed::<[u8], LayoutBuffer<Global>>());
+        assert!(!is_managed::<u32, OnceBuffer<u32, Global>>());
+        assert!(!is_managed::<[mem::MaybeUninit<u32>], TrackedBuffer<u32>>());
+    }
+
+    #[test]
+    fn ptr_eq() {
+        let buffer = AllocatedBuffer::<[u32]>::new_slice(&Global, 2).unwrap();
+        let ptr = buffer.into_raw();
+        let a = unsafe { AllocatedBuffer::<[u32]>::from_raw(ptr) };
+        let b = mem::ManuallyDrop::new(unsafe { AllocatedBuffer::<[u32]>::from_raw(ptr) });
+        let c = AllocatedBuffer::<[u32]>::new_slice(&Global, 2).unwrap();
+
+        assert!(a.ptr_eq(&b));
+        assert!(!a.ptr_eq(&c));
+
+        a.free(&Global);
+        c.free(&Global);
+    }
+
+    #[test]
+    fn grow_zeroed() {
+        let counting = Counting::default();
+        let mut buffer = AllocatedBuffer::<[u8], _>::new_slice(&&counting, 4).unwrap();
+        let bytes: *mut [u8] = buffer.as_mut_ptr(&&counting);
+        unsafe { bytes.as_mut_ptr().write_bytes(0xFF, 4) };
+
+        buffer.grow_zeroed(&&counting, 64).unwrap();
+        assert_eq!(buffer.capacity(), 64);
+        assert_eq!(counting.grows.get(), 1);
+
+        let bytes = unsafe { Box::<[u8], _>::from_buffer(buffer, &counting) };
+        assert_eq!(bytes[..4], [0xFF; 4]);
+        assert!(bytes[4..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn exact_zeroed_slice_with_excess_capacity() {
+        let rounding = Rounding::default();
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice_zeroed_exact(&&rounding, 5).unwrap();
+        assert_eq!(buffer.capacity(), 5);
+        assert_eq!(rounding.counting.shrinks.get(), 1);
+
+        let values = unsafe { Box::<[u32], _>::from_buffer(buffer, &rounding) };
+        assert_eq!(*values, [0; 5]);
+
+        drop(values);
+        assert_eq!(rounding.counting.deallocations.get(), 1);
+        assert_eq!(rounding.counting.bytes.get(), 0);
+    }
+
+    #[test]
+    fn exact_slice_zero_sized() {
+        let buffer = AllocatedBuffer::<[()], _>::new_slice_exact(&Global, 3).unwrap();
+
+        assert_eq!(buffer.capacity(), 3);
+    }
+
+    #[test]
+    fn odd_allocation_size() {
+        let allocator = Odd { short: false };
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice(&allocator, 3).unwrap();
+
+        assert_eq!(buffer.capacity(), 3);
+
+        buffer.free(&allocator);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the allocator returned less memory than requested")]
+    fn short_allocation_size() {
+        let allocator = Odd { short: true };
+        let _ = AllocatedBuffer::<[u32], _>::new_slice(&allocator, 3);
+    }
+
+    #[test]
+    fn grow_capacity() {
+        assert_eq!(checked_grow_capacity(0, 3), Ok(3));
+        assert_eq!(checked_grow_capacity(4, 5), Ok(8));
+        assert_eq!(checked_grow_capacity(4, 20), Ok(20));
+    }
+
+    #[test]
+    fn grow_capacity_overflow() {
+        let max = isize::MAX as usize;
+        assert_eq!(checked_grow_capacity(max / 2, max), Ok(max));
+        assert_eq!(
+            checked_grow_capacity(max / 2 + 1, max),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(
+            checked_grow_capacity(0, max + 1),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert_eq!(
+            checked_grow_capacity(usize::MAX / 2 + 1, usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+    }
+
+    #[test]
+    fn copy_within_from() {
+        let mut src = AllocatedBuffer::<[u32], _>::new_slice_exact(&Global, 3).unwrap();
+        let mut dst = AllocatedBuffer::<[u32], _>::new_slice_zeroed_exact(&Global, 5).unwrap();
+        let values: *mut [u32] = src.as_mut_ptr(&Global);
+        unsafe { (&mut *values).copy_from_slice(&[1, 2, 3]) };
+
+        dst.copy_within_from(&src, 3);
+        let copied: *const [u32] = dst.as_ptr(&Global);
+        assert_eq!(unsafe { &*copied }, [1, 2, 3, 0, 0]);
+
+        src.free(&Global);
+        dst.free(&Global);
+    }
+
+    #[test]
+    fn copy_within_from_overlapping() {
+        let mut buffer = AllocatedBuffer::<[u32], _>::new_slice_exact(&Global, 5).unwrap();
+        let values: *mut [u32] = buffer.as_mut_ptr(&Global);
+        unsafe { (&mut *values).copy_from_slice(&[1, 2, 3, 4, 5]) };
+
+        let ptr = buffer.ptr.as_non_null_ptr();
+        let src = unsafe { AllocatedBuffer::<[u32]>::from_raw_parts(ptr, 4) };
+        let mut dst = unsafe {
+            AllocatedBuffer::<[u32]>::from_raw_parts(NonNull::new_unchecked(ptr.as_ptr().add(1)), 4)
+        };
+        dst.copy_within_from(&src, 4);
+        assert_eq!(unsafe { &*values }, [1, 1, 2, 3, 4]);
+
+        mem::forget((src, dst));
+        buffer.free(&Global);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "cannot copy 4 elements from a buffer with a capacity of 3 into a buffer with \
+                    a capacity of 5"
+    )]
+    fn copy_within_from_out_of_bounds() {
+        let src = AllocatedBuffer::<[u32], _>::new_slice_exact(&Global, 3).unwrap();
+        let mut dst = AllocatedBuffer::<[u32], _>::new_slice_exact(&Global, 5).unwrap();
+        dst.copy_within_from(&src, 4);
+    }
+
+    #[test]
+    fn alias() {
+        let counting = Counting::default();
+        let mut buffer = AllocatedBuffer::<u32, _>::new_in(&counting).unwrap();
+        let value: *mut u32 = buffer.as_mut_ptr(&counting);
+        unsafe { value.write(7) };
+
+        let alias = unsafe { buffer.alias() };
+        let aliased: *const u32 = alias.as_ptr(&counting);
+        assert_eq!(unsafe { *aliased }, 7);
+
+        alias.free(&counting);
+        drop(buffer);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+}
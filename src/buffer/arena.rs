@@ -0,0 +1,144 @@
+use alloc::alloc::{handle_alloc_error, Global};
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    ptr::NonNull,
+};
+
+/// A bump allocator, which releases all of its allocations at once.
+///
+/// The arena reserves a fixed block of memory on the global heap up-front and hands out
+/// consecutive parts of it. Deallocating is a no-op, the memory is reclaimed by [`reset`], which
+/// requires unique access to the arena. As boxes in the arena borrow it as external data, the
+/// borrow checker ensures that no box outlives a reset.
+///
+/// [`reset`]: Self::reset
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use storages::{
+///     boxed::Box,
+///     buffer::{AllocatedBuffer, Arena},
+/// };
+///
+/// let mut arena = Arena::with_capacity(64);
+/// let five = Box::new_in(5_u32, AllocatedBuffer::new_in(&&arena)?, &arena);
+/// assert_eq!(*five, 5);
+/// assert_eq!(arena.used(), 4);
+///
+/// drop(five);
+/// arena.reset();
+/// assert_eq!(arena.used(), 0);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+#[cfg_attr(doc, doc(cfg(feature = "arena")))]
+pub struct Arena {
+    memory: NonNull<u8>,
+    capacity: usize,
+    offset: Cell<usize>,
+}
+
+impl Arena {
+    /// Creates an arena, which can hold up to `capacity` bytes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let layout = Layout::array::<u8>(capacity).expect("capacity overflow");
+        let memory = if capacity == 0 {
+            NonNull::dangling()
+        } else {
+            Global
+                .allocate(layout)
+                .unwrap_or_else(|_| handle_alloc_error(layout))
+                .cast()
+        };
+        Self {
+            memory,
+            capacity,
+            offset: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of bytes the arena can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes handed out since the last reset, including padding.
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    /// Reclaims the memory of all allocations.
+    pub fn reset(&mut self) {
+        self.offset.set(0);
+    }
+}
+
+unsafe impl Allocator for Arena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let base = self.memory.as_ptr() as usize;
+        let start = (base + self.offset.get() + layout.align() - 1) & !(layout.align() - 1);
+        let end = start.checked_add(layout.size()).ok_or(AllocError)?;
+        if end > base + self.capacity {
+            return Err(AllocError);
+        }
+        self.offset.set(end - base);
+        let ptr = unsafe { NonNull::new_unchecked(self.memory.as_ptr().add(start - base)) };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: Layout) {}
+}
+
+impl Drop for Arena {
+    fn drop(&mut self) {
+        if self.capacity != 0 {
+            unsafe {
+                Global.deallocate(
+                    self.memory,
+                    Layout::from_size_align_unchecked(self.capacity, 1),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boxed::Box, buffer::AllocatedBuffer};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn reset_and_reuse() {
+        let mut arena = Arena::with_capacity(1024);
+        for round in 0..3_u64 {
+            let boxes: Vec<_> = (0..100)
+                .map(|value| {
+                    let buffer = AllocatedBuffer::new_in(&&arena).unwrap();
+                    Box::new_in(round * 100 + value, buffer, &arena)
+                })
+                .collect();
+            assert_eq!(arena.used(), 800);
+            assert!(
+                boxes
+                    .iter()
+                    .zip(0..)
+                    .all(|(value, i)| **value == round * 100 + i)
+            );
+
+            drop(boxes);
+            arena.reset();
+            assert_eq!(arena.used(), 0);
+        }
+    }
+
+    #[test]
+    fn exhausted() {
+        let arena = Arena::with_capacity(8);
+        assert!(arena.allocate(Layout::new::<u64>()).is_ok());
+        assert!(arena.allocate(Layout::new::<u8>()).is_err());
+    }
+}
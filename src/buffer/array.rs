@@ -1,26 +1,71 @@
 use core::{mem, ptr};
 
-use super::Buffer;
+use super::{Buffer, ConstCapacity, ReadBuffer};
 
-impl<T, const N: usize> Buffer<[T]> for [T; N] {
+impl<T, const N: usize> ReadBuffer<[T]> for [T; N] {
     type ExternalData = ();
 
     fn as_ptr(&self, _data: &Self::ExternalData) -> *const [T] {
         ptr::slice_from_raw_parts(<[_]>::as_ptr(self).cast(), N)
     }
+}
 
+impl<T, const N: usize> Buffer<[T]> for [T; N] {
     fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [T] {
         ptr::slice_from_raw_parts_mut(<[_]>::as_mut_ptr(self).cast(), N)
     }
 }
 
-impl<T, const N: usize> Buffer<[T]> for [mem::MaybeUninit<T>; N] {
+impl<T, const N: usize> ConstCapacity for [T; N] {
+    const CAPACITY: usize = N;
+}
+
+/// Views an array of uninitialized elements as initialized slice.
+///
+/// The uninitialized view `Buffer<[MaybeUninit<T>]>` is provided by the implementation for
+/// `[T; N]`, so a box over `[MaybeUninit<T>; N]` exposes `MaybeUninit<T>` elements. This
+/// initialized view is only reachable through the unsafe `assume_init` or `from_buffer`, which
+/// require the caller to guarantee, that all elements are initialized.
+///
+/// # Examples
+///
+/// ```
+/// use std::mem;
+/// use storages::boxed::Box;
+///
+/// let buffer = [mem::MaybeUninit::<u32>::uninit(); 3];
+/// let mut values = Box::new_uninit_slice_in(buffer, ());
+///
+/// let slice: &mut [mem::MaybeUninit<u32>] = &mut values;
+/// for (i, value) in slice.iter_mut().enumerate() {
+///     *value = mem::MaybeUninit::new(i as u32);
+/// }
+///
+/// let values = unsafe { values.assume_init() };
+///
+/// assert_eq!(*values, [0, 1, 2]);
+/// ```
+///
+/// Before calling `assume_init`, the elements can't be accessed as initialized values:
+///
+/// ```compile_fail
+/// use std::mem;
+/// use storages::boxed::RawBox;
+///
+/// let buffer = [mem::MaybeUninit::<u32>::uninit(); 3];
+/// let values = RawBox::new_uninit_slice_in(buffer);
+///
+/// let slice: &[u32] = values.as_ref(&());
+/// ```
+impl<T, const N: usize> ReadBuffer<[T]> for [mem::MaybeUninit<T>; N] {
     type ExternalData = ();
 
     fn as_ptr(&self, _data: &Self::ExternalData) -> *const [T] {
         ptr::slice_from_raw_parts(<[_]>::as_ptr(self).cast(), N)
     }
+}
 
+impl<T, const N: usize> Buffer<[T]> for [mem::MaybeUninit<T>; N] {
     fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [T] {
         ptr::slice_from_raw_parts_mut(<[_]>::as_mut_ptr(self).cast(), N)
     }
@@ -0,0 +1,161 @@
+use super::{Buffer, ReadBuffer};
+use core::{mem, ptr};
+
+/// A read-only buffer, which views data with a `'static` lifetime without allocating.
+///
+/// The data is never freed and can't be mutated, so `StaticBuffer` only implements
+/// [`ReadBuffer`].
+///
+/// # Examples
+///
+/// ```
+/// use storages::{boxed::Box, buffer::StaticBuffer};
+///
+/// let data = unsafe { Box::from_buffer(StaticBuffer::new(b"data"), ()) };
+///
+/// assert_eq!(*data, *b"data");
+/// ```
+///
+/// Mutating the data is rejected at compile time:
+///
+/// ```compile_fail
+/// use storages::{boxed::Box, buffer::StaticBuffer};
+///
+/// let mut data = unsafe { Box::from_buffer(StaticBuffer::new(b"data"), ()) };
+/// data[0] = b'D';
+/// ```
+pub struct StaticBuffer<T: 'static> {
+    data: &'static [T],
+}
+
+impl<T> StaticBuffer<T> {
+    /// Creates a buffer viewing `data`.
+    pub const fn new(data: &'static [T]) -> Self {
+        Self { data }
+    }
+}
+
+impl<T> ReadBuffer<[T]> for StaticBuffer<T> {
+    type ExternalData = ();
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [T] {
+        self.data
+    }
+}
+
+/// A buffer over caller-provided scratch space, which is never freed.
+///
+/// This allows functions to build a box over a buffer on the caller's stack. As a [`Buffer<[T]>`],
+/// all elements are exposed, so a box in `SliceBuffer` is usually created uninitialized.
+///
+/// [`Buffer<[T]>`]: crate::buffer::Buffer
+///
+/// # Examples
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use storages::{boxed::Box, buffer::SliceBuffer};
+///
+/// let mut scratch = [MaybeUninit::<u32>::uninit(); 8];
+/// let mut values = Box::<[u32], _>::new_uninit_slice_in(SliceBuffer::new(&mut scratch), ());
+///
+/// for (i, value) in values.iter_mut().enumerate() {
+///     *value = MaybeUninit::new(i as u32);
+/// }
+/// let values = unsafe { values.assume_init() };
+///
+/// assert_eq!(*values, [0, 1, 2, 3, 4, 5, 6, 7]);
+/// ```
+pub struct SliceBuffer<'a, T> {
+    data: &'a mut [mem::MaybeUninit<T>],
+}
+
+impl<'a, T> SliceBuffer<'a, T> {
+    /// Creates a buffer over `data`.
+    pub fn new(data: &'a mut [mem::MaybeUninit<T>]) -> Self {
+        Self { data }
+    }
+
+    /// Returns the first `len` elements as an initialized slice.
+    ///
+    /// This is useful for collections, which track the number of initialized elements
+    /// separately, as the remaining elements don't have to be viewed as `[T]`.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` exceeds the length of the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::mem::MaybeUninit;
+    /// use storages::buffer::SliceBuffer;
+    ///
+    /// let mut scratch = [MaybeUninit::<u32>::uninit(); 4];
+    /// scratch[0] = MaybeUninit::new(1);
+    /// let buffer = SliceBuffer::new(&mut scratch);
+    ///
+    /// assert_eq!(unsafe { buffer.init_prefix(&(), 1) }, [1]);
+    /// ```
+    pub unsafe fn init_prefix(&self, _data: &(), len: usize) -> &[T] {
+        let prefix: *const [mem::MaybeUninit<T>] = &self.data[..len];
+        &*(prefix as *const [T])
+    }
+}
+
+impl<T> ReadBuffer<[T]> for SliceBuffer<'_, T> {
+    type ExternalData = ();
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [T] {
+        ptr::slice_from_raw_parts(self.data.as_ptr().cast(), self.data.len())
+    }
+}
+
+impl<T> Buffer<[T]> for SliceBuffer<'_, T> {
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut(self.data.as_mut_ptr().cast(), self.data.len())
+    }
+}
+
+impl<T> ReadBuffer<[mem::MaybeUninit<T>]> for SliceBuffer<'_, T> {
+    type ExternalData = ();
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [mem::MaybeUninit<T>] {
+        &*self.data
+    }
+}
+
+impl<T> Buffer<[mem::MaybeUninit<T>]> for SliceBuffer<'_, T> {
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [mem::MaybeUninit<T>] {
+        &mut *self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_prefix() {
+        let mut scratch = [mem::MaybeUninit::<u32>::uninit(); 8];
+        for (i, value) in scratch.iter_mut().take(3).enumerate() {
+            *value = mem::MaybeUninit::new(i as u32 + 1);
+        }
+        let buffer = SliceBuffer::new(&mut scratch);
+
+        assert_eq!(unsafe { buffer.init_prefix(&(), 3) }, [1, 2, 3]);
+        assert!(unsafe { buffer.init_prefix(&(), 0) }.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn init_prefix_out_of_bounds() {
+        let mut scratch = [mem::MaybeUninit::<u32>::uninit(); 8];
+        let buffer = SliceBuffer::new(&mut scratch);
+        unsafe { buffer.init_prefix(&(), 9) };
+    }
+}
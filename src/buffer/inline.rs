@@ -0,0 +1,258 @@
+use super::{AllocateBuffer, Buffer, ConstCapacity, GrowableBuffer, ReadBuffer, TakeInitialized};
+use alloc::collections::TryReserveError;
+use core::{alloc::AllocError, mem, ptr};
+
+/// A buffer with a compile-time capacity of `N` elements, which is stored inline.
+///
+/// `InlineBuffer` tracks how many elements are initialized, so it can be used as a backend for
+/// fixed-capacity collections without any allocation. As a [`Buffer<[T]>`], it exposes the
+/// initialized elements only.
+///
+/// [`Buffer<[T]>`]: crate::buffer::Buffer
+///
+/// # Examples
+///
+/// ```
+/// use storages::{boxed::Box, buffer::InlineBuffer};
+///
+/// let mut buffer = InlineBuffer::<u32, 4>::new();
+/// buffer.try_push(1).unwrap();
+/// buffer.try_push(2).unwrap();
+///
+/// let values = unsafe { Box::<[u32], _>::from_buffer(buffer, ()) };
+///
+/// assert_eq!(*values, [1, 2]);
+/// ```
+pub struct InlineBuffer<T, const N: usize> {
+    buffer: [mem::MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> InlineBuffer<T, N> {
+    /// The number of elements the buffer can hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::buffer::InlineBuffer;
+    ///
+    /// const CAPACITY: usize = InlineBuffer::<u8, 16>::CAPACITY;
+    /// let bytes = [0_u8; CAPACITY];
+    ///
+    /// assert_eq!(bytes.len(), 16);
+    /// ```
+    pub const CAPACITY: usize = N;
+
+    /// Fails to evaluate if `N` elements of `T` exceed `isize::MAX` bytes.
+    const SIZE_CHECK: () = assert!(
+        match mem::size_of::<T>().checked_mul(N) {
+            Some(size) => size <= isize::MAX as usize,
+            None => false,
+        },
+        "`InlineBuffer` exceeds the maximum size of `isize::MAX` bytes"
+    );
+
+    /// Creates an empty buffer.
+    ///
+    /// If the buffer exceeds `isize::MAX` bytes, this fails to compile:
+    ///
+    /// ```compile_fail
+    /// use storages::buffer::InlineBuffer;
+    ///
+    /// let buffer = InlineBuffer::<u64, { usize::MAX / 4 }>::new();
+    /// ```
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::SIZE_CHECK;
+        Self {
+            buffer: mem::MaybeUninit::uninit_array(),
+            len: 0,
+        }
+    }
+
+    /// Creates an empty buffer with all bytes of the storage being `0`.
+    ///
+    /// Combined with [`assume_init`], this creates a buffer of `N` zeroed elements without any
+    /// allocation.
+    ///
+    /// [`assume_init`]: Self::assume_init
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::{boxed::InlineBox, buffer::InlineBuffer};
+    ///
+    /// let buffer = InlineBuffer::<u32, 4>::new_zeroed();
+    /// let values = InlineBox::from_inline(unsafe { buffer.assume_init() });
+    ///
+    /// assert_eq!(*values, [0; 4]);
+    /// ```
+    pub fn new_zeroed() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::SIZE_CHECK;
+        Self {
+            buffer: unsafe { mem::MaybeUninit::zeroed().assume_init() },
+            len: 0,
+        }
+    }
+
+    /// Marks all `N` elements as initialized.
+    ///
+    /// # Safety
+    ///
+    /// As with [`MaybeUninit::assume_init`], it is up to the caller to guarantee that all
+    /// elements really are in an initialized state, e.g. because the buffer was created by
+    /// [`new_zeroed`] and all-zero bytes are a valid `T`.
+    ///
+    /// [`MaybeUninit::assume_init`]: core::mem::MaybeUninit::assume_init
+    /// [`new_zeroed`]: Self::new_zeroed
+    pub unsafe fn assume_init(mut self) -> Self {
+        self.len = N;
+        self
+    }
+
+    /// Returns the number of initialized elements.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no element is initialized.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if all `N` elements are initialized.
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Appends an element to the back of the buffer.
+    ///
+    /// If the buffer is full, `value` is returned back in `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::buffer::InlineBuffer;
+    ///
+    /// let mut buffer = InlineBuffer::<u32, 2>::new();
+    ///
+    /// assert_eq!(buffer.try_push(1), Ok(()));
+    /// assert_eq!(buffer.try_push(2), Ok(()));
+    /// assert_eq!(buffer.try_push(3), Err(3));
+    /// assert_eq!(buffer.len(), 2);
+    /// ```
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.buffer[self.len] = mem::MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last element from the buffer and returns it, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::buffer::InlineBuffer;
+    ///
+    /// let mut buffer = InlineBuffer::<u32, 2>::new();
+    /// buffer.try_push(1).unwrap();
+    /// buffer.try_push(2).unwrap();
+    ///
+    /// assert_eq!(buffer.pop(), Some(2));
+    /// assert_eq!(buffer.pop(), Some(1));
+    /// assert_eq!(buffer.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        unsafe { Some(self.buffer[self.len].as_ptr().read()) }
+    }
+}
+
+impl<T, const N: usize> Default for InlineBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for InlineBuffer<T, N> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(Buffer::<[T]>::as_mut_ptr(self, &())) }
+    }
+}
+
+impl<T, const N: usize> ReadBuffer<[T]> for InlineBuffer<T, N> {
+    type ExternalData = ();
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [T] {
+        ptr::slice_from_raw_parts(<[_]>::as_ptr(&self.buffer).cast(), self.len)
+    }
+}
+
+impl<T, const N: usize> Buffer<[T]> for InlineBuffer<T, N> {
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [T] {
+        ptr::slice_from_raw_parts_mut(<[_]>::as_mut_ptr(&mut self.buffer).cast(), self.len)
+    }
+}
+
+/// Exposes the whole storage as uninitialized elements.
+///
+/// Unlike the initialized view, this spans all `N` elements regardless of [`len`]. Collections,
+/// which track their elements themselves, use this view. They take over the initialized elements
+/// when wrapping the buffer, so [`len`] is `0` afterwards and no element is dropped twice.
+///
+/// [`len`]: InlineBuffer::len
+impl<T, const N: usize> ReadBuffer<[mem::MaybeUninit<T>]> for InlineBuffer<T, N> {
+    type ExternalData = ();
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [mem::MaybeUninit<T>] {
+        &self.buffer[..]
+    }
+}
+
+impl<T, const N: usize> Buffer<[mem::MaybeUninit<T>]> for InlineBuffer<T, N> {
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [mem::MaybeUninit<T>] {
+        &mut self.buffer[..]
+    }
+}
+
+impl<T, const N: usize> TakeInitialized for InlineBuffer<T, N> {
+    fn take_initialized(&mut self) -> usize {
+        mem::replace(&mut self.len, 0)
+    }
+}
+
+/// Succeeds, if `len + additional` doesn't exceed `N`.
+impl<T, const N: usize> GrowableBuffer<[mem::MaybeUninit<T>]> for InlineBuffer<T, N> {
+    fn try_reserve(
+        &mut self,
+        _data: &Self::ExternalData,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        match len.checked_add(additional) {
+            Some(required) if required <= N => Ok(()),
+            _ => Err(TryReserveError::CapacityOverflow),
+        }
+    }
+}
+
+impl<T, const N: usize> ConstCapacity for InlineBuffer<T, N> {
+    const CAPACITY: usize = N;
+}
+
+/// Creates an empty buffer, if `len` doesn't exceed `N`.
+impl<T, const N: usize> AllocateBuffer<[mem::MaybeUninit<T>]> for InlineBuffer<T, N> {
+    fn allocate(_data: &Self::ExternalData, len: usize) -> Result<Self, AllocError> {
+        if len > N {
+            return Err(AllocError);
+        }
+        Ok(Self::new())
+    }
+}
@@ -0,0 +1,117 @@
+use super::{dangling, Buffer, ReadBuffer, UnmanagedBuffer};
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    marker::PhantomData,
+    ptr::{self, NonNull},
+};
+
+/// A byte buffer allocated with an arbitrary [`Layout`].
+///
+/// Unlike [`AllocatedBuffer<[u8]>`], which always assumes an alignment of `1`, the buffer stores
+/// the layout it was allocated with and passes it back to the allocator when it's freed. This is
+/// useful for data, whose layout is only known at runtime, e.g. structs passed over FFI. The
+/// memory is zeroed on allocation.
+///
+/// [`AllocatedBuffer<[u8]>`]: crate::buffer::AllocatedBuffer
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::{Global, Layout};
+/// use storages::buffer::{LayoutBuffer, ReadBuffer, UnmanagedBuffer};
+///
+/// let layout = Layout::from_size_align(24, 8).unwrap();
+/// let buffer = LayoutBuffer::new_in(layout, &Global)?;
+/// assert_eq!(buffer.layout(), layout);
+///
+/// let bytes: *const [u8] = buffer.as_ptr(&Global);
+/// assert_eq!(bytes as *const u8 as usize % 8, 0);
+///
+/// buffer.free(&Global);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub struct LayoutBuffer<A: ?Sized> {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    _marker: PhantomData<fn(*const A)>,
+}
+
+impl<A: ?Sized + Allocator> LayoutBuffer<A> {
+    /// Allocates zeroed memory for `layout` in the provided allocator.
+    ///
+    /// Zero-sized layouts don't allocate.
+    pub fn new_in(layout: Layout, allocator: &A) -> Result<Self, AllocError> {
+        let ptr = if layout.size() == 0 {
+            dangling(layout)
+        } else {
+            allocator.allocate_zeroed(layout)?.cast()
+        };
+        Ok(Self {
+            ptr,
+            layout,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<A: ?Sized> LayoutBuffer<A> {
+    /// Returns the layout the buffer was allocated with.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+impl<A: ?Sized + Allocator> ReadBuffer<[u8]> for LayoutBuffer<A> {
+    type ExternalData = A;
+
+    fn as_ptr(&self, _data: &Self::ExternalData) -> *const [u8] {
+        ptr::slice_from_raw_parts(self.ptr.as_ptr(), self.layout.size())
+    }
+}
+
+impl<A: ?Sized + Allocator> Buffer<[u8]> for LayoutBuffer<A> {
+    fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut [u8] {
+        ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size())
+    }
+}
+
+impl<A: ?Sized + Allocator> UnmanagedBuffer<[u8]> for LayoutBuffer<A> {
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        if self.layout.size() != 0 {
+            allocator.deallocate(self.ptr, self.layout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Recording;
+
+    #[test]
+    fn frees_with_layout() {
+        let allocator = Recording::default();
+        let layout = Layout::from_size_align(17, 8).unwrap();
+        LayoutBuffer::new_in(layout, &allocator)
+            .unwrap()
+            .free(&allocator);
+
+        assert_eq!(allocator.allocated.get(), Some(layout));
+        assert_eq!(allocator.deallocated.get(), Some(layout));
+    }
+
+    #[test]
+    fn zero_sized() {
+        let allocator = Recording::default();
+        let layout = Layout::from_size_align(0, 16).unwrap();
+        let buffer = LayoutBuffer::new_in(layout, &allocator).unwrap();
+        let bytes: *const [u8] = buffer.as_ptr(&allocator);
+        assert_eq!(bytes.cast::<u8>() as usize % 16, 0);
+
+        buffer.free(&allocator);
+        assert_eq!(allocator.allocated.get(), None);
+        assert_eq!(allocator.deallocated.get(), None);
+    }
+}
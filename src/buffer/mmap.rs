@@ -0,0 +1,162 @@
+use super::dangling;
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::{self, NonNull},
+};
+use std::os::raw::{c_int, c_long, c_void};
+
+// The declarations and constants are only checked against the targets `mmap` is compiled for:
+// 64-bit Linux on x86-64 and AArch64 and macOS, where `off_t` is a `c_long`.
+extern "C" {
+    fn mmap(
+        addr: *mut c_void,
+        len: usize,
+        prot: c_int,
+        flags: c_int,
+        fd: c_int,
+        offset: c_long,
+    ) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+const PROT_READ: c_int = 0x1;
+const PROT_WRITE: c_int = 0x2;
+const MAP_PRIVATE: c_int = 0x2;
+#[cfg(target_os = "linux")]
+const MAP_ANONYMOUS: c_int = 0x20;
+#[cfg(target_os = "macos")]
+const MAP_ANONYMOUS: c_int = 0x1000;
+const MAP_FAILED: *mut c_void = !0 as *mut c_void;
+
+/// The smallest page size on the supported platforms. Every mapping is aligned to at least this.
+const PAGE_SIZE: usize = 4096;
+
+/// An allocator, which maps anonymous memory pages for every allocation.
+///
+/// Every allocation occupies at least one page, so this is only suitable for large values. The
+/// mapped memory is always zeroed. Alignments up to the page size are supported.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use storages::{
+///     boxed::Box,
+///     buffer::{AllocatedBuffer, MmapAllocator},
+/// };
+///
+/// let buffer = AllocatedBuffer::new_in(&MmapAllocator)?;
+/// let values = Box::new_in([1_u64; 1024], buffer, MmapAllocator);
+///
+/// assert_eq!(values.iter().sum::<u64>(), 1024);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+#[cfg_attr(
+    doc,
+    doc(cfg(all(
+        feature = "std",
+        target_pointer_width = "64",
+        any(
+            all(
+                target_os = "linux",
+                any(target_arch = "x86_64", target_arch = "aarch64")
+            ),
+            target_os = "macos"
+        )
+    )))
+)]
+#[derive(Copy, Clone, Default, Debug)]
+pub struct MmapAllocator;
+
+unsafe impl Allocator for MmapAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.align() > PAGE_SIZE {
+            return Err(AllocError);
+        }
+        if layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(dangling(layout), 0));
+        }
+
+        let ptr = unsafe {
+            mmap(
+                ptr::null_mut(),
+                layout.size(),
+                PROT_READ | PROT_WRITE,
+                MAP_PRIVATE | MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if ptr == MAP_FAILED {
+            return Err(AllocError);
+        }
+        let ptr = NonNull::new(ptr.cast()).ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // anonymous mappings are zero-initialized
+        self.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            let result = munmap(ptr.as_ptr().cast(), layout.size());
+            debug_assert_eq!(result, 0, "failed to unmap memory");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boxed::Box, buffer::AllocatedBuffer};
+    use core::cell::Cell;
+
+    struct Counted<'a> {
+        allocations: &'a Cell<usize>,
+        deallocations: &'a Cell<usize>,
+    }
+
+    unsafe impl Allocator for Counted<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocations.set(self.allocations.get() + 1);
+            MmapAllocator.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.deallocations.set(self.deallocations.get() + 1);
+            MmapAllocator.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn allocate_and_free() {
+        let allocations = Cell::new(0);
+        let deallocations = Cell::new(0);
+        let allocator = Counted {
+            allocations: &allocations,
+            deallocations: &deallocations,
+        };
+
+        let buffer = AllocatedBuffer::new_in(&allocator).unwrap();
+        let mut values = Box::new_in([0_u32; 3000], buffer, allocator);
+        values[2999] = 5;
+
+        assert_eq!(values.as_ptr() as usize % PAGE_SIZE, 0);
+        assert_eq!(values[2999], 5);
+        assert_eq!(allocations.get(), 1);
+
+        drop(values);
+        assert_eq!(deallocations.get(), 1);
+    }
+
+    #[test]
+    fn excessive_alignment() {
+        let layout = Layout::from_size_align(8, PAGE_SIZE * 2).unwrap();
+
+        assert!(MmapAllocator.allocate(layout).is_err());
+    }
+}
@@ -1,25 +1,128 @@
+use ::alloc::collections::TryReserveError;
+use core::{alloc::AllocError, ptr};
+
+mod aligned;
 mod alloc;
+#[cfg(feature = "arena")]
+mod arena;
 mod array;
+mod borrowed;
+mod inline;
+mod layout;
+#[cfg(all(
+    feature = "std",
+    target_pointer_width = "64",
+    any(
+        all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        ),
+        target_os = "macos"
+    )
+))]
+mod mmap;
+mod once;
+mod slab;
+mod small;
+mod stats;
+mod strategy;
+mod tracked;
+mod union;
 
-pub use self::alloc::*;
+#[cfg(feature = "arena")]
+pub use self::arena::Arena;
+#[cfg(all(
+    feature = "std",
+    target_pointer_width = "64",
+    any(
+        all(
+            target_os = "linux",
+            any(target_arch = "x86_64", target_arch = "aarch64")
+        ),
+        target_os = "macos"
+    )
+))]
+pub use self::mmap::MmapAllocator;
+pub use self::{
+    aligned::*,
+    alloc::*,
+    borrowed::*,
+    inline::*,
+    layout::*,
+    once::*,
+    slab::*,
+    small::*,
+    stats::*,
+    strategy::*,
+    tracked::*,
+    union::*,
+};
 
-/// Backend for collection types like `Box` and `Vec`.
+/// Read-only backend for collection types like `Box` and `Vec`.
 ///
 /// Every buffer may require an external datum, which is passed every time the buffer is accessed.
 /// This way the implementation does not require the data to store which might save some memory.
-pub trait Buffer<T: ?Sized> {
+pub trait ReadBuffer<T: ?Sized> {
     /// Data required to be passed at every interaction with the buffer.
     type ExternalData: ?Sized;
 
+    /// Whether the buffer releases its resources itself.
+    ///
+    /// Managed buffers like arrays are released when they are dropped, while unmanaged buffers
+    /// implement [`UnmanagedBuffer`] and have to be freed explicitly. This allows generic code to
+    /// branch on the kind of the buffer without relying on specialization. Defaults to `false`
+    /// for implementors of [`UnmanagedBuffer`] and to `true` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use storages::buffer::{AllocatedBuffer, ReadBuffer};
+    ///
+    /// assert!(<[u32; 3] as ReadBuffer<[u32]>>::IS_MANAGED);
+    /// assert!(!<AllocatedBuffer<u32> as ReadBuffer<u32>>::IS_MANAGED);
+    /// ```
+    const IS_MANAGED: bool = <Self as Managed<T>>::IS_MANAGED;
+
     /// Returns a shared pointer to the buffered datum.
     fn as_ptr(&self, data: &Self::ExternalData) -> *const T;
+}
+
+/// Derives the default of [`ReadBuffer::IS_MANAGED`] from whether the buffer is unmanaged.
+trait Managed<T: ?Sized> {
+    const IS_MANAGED: bool;
+}
+
+impl<T: ?Sized, B: ?Sized> Managed<T> for B {
+    default const IS_MANAGED: bool = true;
+}
+
+impl<T: ?Sized, B: ?Sized + UnmanagedBuffer<T>> Managed<T> for B {
+    const IS_MANAGED: bool = false;
+}
+
+/// Hands the elements a buffer initialized itself over to a collection.
+///
+/// Collections like `BufferVec` track their elements themselves. A buffer like [`InlineBuffer`]
+/// may already contain elements, which it would drop itself. Taking them transfers their
+/// ownership to the collection and returns how many leading elements are initialized.
+pub(crate) trait TakeInitialized {
+    fn take_initialized(&mut self) -> usize;
+}
 
+impl<B: ?Sized> TakeInitialized for B {
+    default fn take_initialized(&mut self) -> usize {
+        0
+    }
+}
+
+/// Backend for collection types like `Box` and `Vec`, which allows mutating the buffered datum.
+pub trait Buffer<T: ?Sized>: ReadBuffer<T> {
     /// Returns a unique pointer to the buffered datum.
     fn as_mut_ptr(&mut self, data: &Self::ExternalData) -> *mut T;
 }
 
 /// A buffer, which uses an external resource
-pub trait UnmanagedBuffer<T: ?Sized>: Buffer<T> {
+pub trait UnmanagedBuffer<T: ?Sized>: ReadBuffer<T> {
     /// Frees the backed resource.
     ///
     /// # Safety
@@ -37,4 +140,145 @@ pub trait UnmanagedBuffer<T: ?Sized>: Buffer<T> {
         }
         drop(self)
     }
+
+    /// Drops the buffered datum and frees the backed resource afterwards.
+    ///
+    /// # Safety
+    ///
+    /// The buffered datum must be initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// # #[cfg(feature = "global")] {
+    /// use std::{alloc::Global, rc::Rc};
+    /// use storages::buffer::{AllocatedBuffer, Buffer, UnmanagedBuffer};
+    ///
+    /// let value = Rc::new(String::from("value"));
+    ///
+    /// let mut buffer = AllocatedBuffer::new()?;
+    /// let ptr: *mut Rc<String> = buffer.as_mut_ptr(&Global);
+    /// unsafe { ptr.write(Rc::clone(&value)) };
+    /// assert_eq!(Rc::strong_count(&value), 2);
+    ///
+    /// unsafe { buffer.free_with_drop(&Global) };
+    /// assert_eq!(Rc::strong_count(&value), 1);
+    /// # }
+    /// # Ok::<(), core::alloc::AllocError>(())
+    /// ```
+    unsafe fn free_with_drop(mut self, allocator: &Self::ExternalData)
+    where
+        Self: Buffer<T> + Sized,
+    {
+        ptr::drop_in_place(self.as_mut_ptr(allocator));
+        self.free(allocator)
+    }
+}
+
+/// A buffer, which can be created for a given number of elements.
+///
+/// This allows collections to be constructed with a capacity regardless of the buffer backing
+/// them. As the created buffer holds no elements yet, this is implemented for the view of
+/// uninitialized elements, which spans at least `len` elements for every buffer.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::{alloc::Global, mem::MaybeUninit};
+/// use storages::buffer::{AllocateBuffer, AllocatedBuffer, InlineBuffer};
+///
+/// fn capacity<B: AllocateBuffer<[MaybeUninit<u32>]>>(
+///     buffer: &B,
+///     data: &B::ExternalData,
+/// ) -> usize {
+///     unsafe { (*buffer.as_ptr(data)).len() }
+/// }
+///
+/// let heap = AllocatedBuffer::<[u32]>::allocate(&Global, 8)?;
+/// assert!(capacity(&heap, &Global) >= 8);
+/// # storages::buffer::UnmanagedBuffer::<[u32]>::free(heap, &Global);
+///
+/// let inline = InlineBuffer::<u32, 8>::allocate(&(), 8)?;
+/// assert_eq!(capacity(&inline, &()), 8);
+///
+/// assert!(InlineBuffer::<u32, 8>::allocate(&(), 9).is_err());
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub trait AllocateBuffer<T: ?Sized>: ReadBuffer<T> + Sized {
+    /// Creates a buffer, which can hold at least `len` elements.
+    ///
+    /// Returns an error if the buffer can't provide the requested capacity.
+    fn allocate(data: &Self::ExternalData, len: usize) -> Result<Self, AllocError>;
+}
+
+/// A buffer, which can grow to hold more elements.
+///
+/// This allows collections, which track their initialized elements themselves, to grow regardless
+/// of the buffer backing them. As the grown part is uninitialized, buffers implement this for
+/// slices of [`MaybeUninit<T>`].
+///
+/// [`MaybeUninit<T>`]: core::mem::MaybeUninit
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::{alloc::Global, mem::MaybeUninit};
+/// use storages::buffer::{AllocatedBuffer, GrowableBuffer, InlineBuffer};
+///
+/// fn reserve<T, B: GrowableBuffer<[MaybeUninit<T>]>>(
+///     buffer: &mut B,
+///     data: &B::ExternalData,
+/// ) -> bool {
+///     buffer.try_reserve(data, 0, 8).is_ok()
+/// }
+///
+/// let mut heap = AllocatedBuffer::<[u32]>::new_slice(&Global, 2)?;
+/// assert!(reserve(&mut heap, &Global));
+/// assert!(heap.capacity() >= 8);
+/// # storages::buffer::UnmanagedBuffer::<[u32]>::free(heap, &Global);
+///
+/// assert!(!reserve(&mut InlineBuffer::<u32, 4>::new(), &()));
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub trait GrowableBuffer<T: ?Sized>: Buffer<T> {
+    /// Ensures that the buffer can hold at least `len + additional` elements.
+    ///
+    /// The first `len` elements are kept. Buffers with a fixed capacity return
+    /// [`TryReserveError::CapacityOverflow`], if they can't hold the elements.
+    fn try_reserve(
+        &mut self,
+        data: &Self::ExternalData,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError>;
+}
+
+/// A buffer, whose capacity is known at compile time.
+///
+/// This allows generic code to size other data or to assert requirements in const contexts
+/// without an instance of the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use storages::buffer::{ConstCapacity, InlineBuffer};
+///
+/// fn capacity<B: ConstCapacity>() -> usize {
+///     B::CAPACITY
+/// }
+///
+/// assert_eq!(capacity::<InlineBuffer<u8, 16>>(), 16);
+/// assert_eq!(capacity::<[u32; 4]>(), 4);
+/// assert_eq!(capacity::<[MaybeUninit<u32>; 8]>(), 8);
+/// ```
+pub trait ConstCapacity {
+    /// The number of elements the buffer can hold.
+    const CAPACITY: usize;
 }
@@ -0,0 +1,204 @@
+use super::{Buffer, ReadBuffer, UnmanagedBuffer};
+use alloc::alloc::handle_alloc_error;
+use core::{
+    alloc::{Allocator, Layout},
+    hint,
+    marker::PhantomData,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicPtr, AtomicU8, Ordering},
+};
+
+const UNINIT: u8 = 0;
+const ALLOCATING: u8 = 1;
+const READY: u8 = 2;
+
+/// A buffer, which allocates its memory on first access.
+///
+/// The memory is allocated zeroed by the allocator passed as external data, when the buffer is
+/// accessed the first time. If multiple threads access the buffer simultaneously, exactly one of
+/// them allocates while the others wait for it, so the buffer can be shared across threads, e.g.
+/// in a `static`.
+///
+/// As the memory is zeroed, a [`Box`] over the buffer can be created by [`Box::from_buffer`] for
+/// types, which are valid when all bytes are `0`.
+///
+/// [`Box`]: crate::boxed::Box
+/// [`Box::from_buffer`]: crate::boxed::Box::from_buffer
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use storages::{boxed::Box, buffer::OnceBuffer};
+///
+/// let buffer = OnceBuffer::<u64, Global>::new();
+/// assert!(!buffer.is_allocated());
+///
+/// let mut value = unsafe { Box::from_buffer(buffer, Global) };
+/// assert_eq!(*value, 0);
+///
+/// *value = 5;
+/// assert_eq!(*value, 5);
+/// ```
+pub struct OnceBuffer<T, A: ?Sized> {
+    ptr: AtomicPtr<T>,
+    state: AtomicU8,
+    _owned: PhantomData<T>,
+    _marker: PhantomData<A>,
+}
+
+impl<T, A: ?Sized> OnceBuffer<T, A> {
+    /// Creates a buffer, which is not yet allocated.
+    pub const fn new() -> Self {
+        Self {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            state: AtomicU8::new(UNINIT),
+            _owned: PhantomData,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the memory has been allocated.
+    pub fn is_allocated(&self) -> bool {
+        self.state.load(Ordering::Acquire) == READY
+    }
+}
+
+impl<T, A: ?Sized> Default for OnceBuffer<T, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: ?Sized + Allocator> OnceBuffer<T, A> {
+    fn get_or_allocate(&self, allocator: &A) -> *mut T {
+        loop {
+            match self.state.compare_exchange_weak(
+                UNINIT,
+                ALLOCATING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let layout = Layout::new::<T>();
+                    let ptr = if let Ok(ptr) = allocator.allocate_zeroed(layout) {
+                        ptr.as_mut_ptr().cast()
+                    } else {
+                        self.state.store(UNINIT, Ordering::Release);
+                        handle_alloc_error(layout)
+                    };
+                    self.ptr.store(ptr, Ordering::Relaxed);
+                    self.state.store(READY, Ordering::Release);
+                    return ptr;
+                }
+                Err(READY) => return self.ptr.load(Ordering::Relaxed),
+                Err(_) => hint::spin_loop(),
+            }
+        }
+    }
+}
+
+impl<T, A: ?Sized + Allocator> ReadBuffer<T> for OnceBuffer<T, A> {
+    type ExternalData = A;
+
+    fn as_ptr(&self, data: &Self::ExternalData) -> *const T {
+        self.get_or_allocate(data)
+    }
+}
+
+impl<T, A: ?Sized + Allocator> Buffer<T> for OnceBuffer<T, A> {
+    fn as_mut_ptr(&mut self, data: &Self::ExternalData) -> *mut T {
+        self.get_or_allocate(data)
+    }
+}
+
+/// Deallocates the memory, if it has been allocated.
+impl<T, A: ?Sized + Allocator> UnmanagedBuffer<T> for OnceBuffer<T, A> {
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        if *self.state.get_mut() == READY {
+            let ptr = NonNull::new_unchecked(*self.ptr.get_mut());
+            allocator.deallocate(ptr.cast(), Layout::new::<T>());
+            *self.state.get_mut() = UNINIT;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boxed::Box;
+    use alloc::{alloc::Global, sync::Arc, vec::Vec};
+    use core::{alloc::AllocError, sync::atomic::AtomicUsize};
+    use std::{sync::Barrier, thread};
+
+    #[derive(Default)]
+    struct AtomicCounting {
+        allocations: AtomicUsize,
+        deallocations: AtomicUsize,
+    }
+
+    unsafe impl Allocator for AtomicCounting {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.allocations.fetch_add(1, Ordering::Relaxed);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.deallocations.fetch_add(1, Ordering::Relaxed);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn allocates_once() {
+        const THREADS: usize = 8;
+
+        let allocator = Arc::new(AtomicCounting::default());
+        let buffer = Arc::new(OnceBuffer::<u64, AtomicCounting>::new());
+        let barrier = Arc::new(Barrier::new(THREADS));
+
+        let threads: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let allocator = Arc::clone(&allocator);
+                let buffer = Arc::clone(&buffer);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    buffer.as_ptr(&allocator) as usize
+                })
+            })
+            .collect();
+        let addresses: Vec<_> = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect();
+
+        assert!(addresses.iter().all(|&address| address == addresses[0]));
+        assert_eq!(allocator.allocations.load(Ordering::Relaxed), 1);
+
+        let buffer = Arc::try_unwrap(buffer).ok().unwrap();
+        buffer.free(&allocator);
+        assert_eq!(allocator.deallocations.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn free_unallocated() {
+        let allocator = AtomicCounting::default();
+        OnceBuffer::<u64, AtomicCounting>::new().free(&allocator);
+
+        assert_eq!(allocator.allocations.load(Ordering::Relaxed), 0);
+        assert_eq!(allocator.deallocations.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn drop_unaccessed_box() {
+        let allocator = AtomicCounting::default();
+        let value = unsafe { Box::<u64, _>::from_buffer(OnceBuffer::new(), &allocator) };
+        drop(value);
+
+        assert_eq!(allocator.allocations.load(Ordering::Relaxed), 0);
+        assert_eq!(allocator.deallocations.load(Ordering::Relaxed), 0);
+    }
+}
@@ -0,0 +1,266 @@
+use super::{Buffer, ReadBuffer, UnmanagedBuffer};
+use crate::boxed::Box;
+#[cfg(feature = "global")]
+use alloc::alloc::handle_alloc_error;
+use alloc::alloc::Global;
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr::{self, NonNull},
+};
+
+/// A slot of the slab, which either holds a value or links to the next free slot.
+union Slot<T> {
+    /// Only sizes the slot, the value is accessed through a cast pointer.
+    #[allow(dead_code)]
+    value: ManuallyDrop<T>,
+    next: *mut Slot<T>,
+}
+
+/// A pool of preallocated slots for values of type `T`.
+///
+/// The slab allocates memory for `capacity` values up-front. Boxes in the slab use a
+/// [`SlabBuffer`] and borrow the slab as external data. Freeing such a box doesn't deallocate
+/// but puts the slot on a free list, from which the next box is served, so allocating and freeing
+/// values of the same type repeatedly doesn't touch the allocator at all.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::buffer::Slab;
+///
+/// let slab = Slab::with_capacity(2);
+/// let first = slab.try_new_box(1).ok().unwrap();
+/// let second = slab.try_new_box(2).ok().unwrap();
+/// assert_eq!(*first + *second, 3);
+///
+/// // The slab is full
+/// assert_eq!(slab.try_new_box(3).err(), Some(3));
+///
+/// drop(first);
+/// let third = slab.try_new_box(3).ok().unwrap();
+/// assert_eq!(*third, 3);
+/// assert_eq!(slab.len(), 2);
+/// # }
+/// ```
+pub struct Slab<T, A: Allocator = Global> {
+    slots: NonNull<Slot<T>>,
+    capacity: usize,
+    /// Slots starting at this index were never handed out.
+    unused: Cell<usize>,
+    /// Head of the free list, or null if no slot was freed.
+    free: Cell<*mut Slot<T>>,
+    len: Cell<usize>,
+    allocator: A,
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> Slab<T> {
+    /// Creates a slab on the global heap, which can hold up to `capacity` values.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global).unwrap_or_else(|_| {
+            handle_alloc_error(Layout::array::<Slot<T>>(capacity).expect("capacity overflow"))
+        })
+    }
+}
+
+impl<T, A: Allocator> Slab<T, A> {
+    /// Creates a slab in the provided allocator, which can hold up to `capacity` values.
+    ///
+    /// This is the only allocation made by the slab.
+    pub fn with_capacity_in(capacity: usize, allocator: A) -> Result<Self, AllocError> {
+        let layout = Layout::array::<Slot<T>>(capacity).map_err(|_| AllocError)?;
+        let slots = if capacity == 0 {
+            NonNull::dangling()
+        } else {
+            allocator.allocate(layout)?.cast()
+        };
+        Ok(Self {
+            slots,
+            capacity,
+            unused: Cell::new(0),
+            free: Cell::new(ptr::null_mut()),
+            len: Cell::new(0),
+            allocator,
+        })
+    }
+
+    /// Returns the number of values the slab can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of occupied slots.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if no slot is occupied.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves `value` into a free slot of the slab.
+    ///
+    /// If the slab is full, `value` is returned back in `Err`.
+    pub fn try_new_box(&self, value: T) -> Result<Box<T, SlabBuffer<'_, T, A>, &Self>, T> {
+        match SlabBuffer::new_in(self) {
+            Ok(buffer) => Ok(Box::new_in(value, buffer, self)),
+            Err(AllocError) => Err(value),
+        }
+    }
+
+    fn take_slot(&self) -> Option<NonNull<Slot<T>>> {
+        let slot = match NonNull::new(self.free.get()) {
+            Some(slot) => {
+                self.free.set(unsafe { slot.as_ref().next });
+                slot
+            }
+            None if self.unused.get() < self.capacity => {
+                let index = self.unused.get();
+                self.unused.set(index + 1);
+                unsafe { NonNull::new_unchecked(self.slots.as_ptr().add(index)) }
+            }
+            None => return None,
+        };
+        self.len.set(self.len.get() + 1);
+        Some(slot)
+    }
+
+    /// Puts the slot on the free list.
+    ///
+    /// # Safety
+    ///
+    /// `slot` must have been taken from this slab and must not be used afterwards.
+    unsafe fn release_slot(&self, slot: NonNull<Slot<T>>) {
+        debug_assert!(
+            slot.as_ptr() >= self.slots.as_ptr()
+                && slot.as_ptr() < self.slots.as_ptr().add(self.unused.get()),
+            "slot doesn't belong to this slab"
+        );
+        (*slot.as_ptr()).next = self.free.get();
+        self.free.set(slot.as_ptr());
+        self.len.set(self.len.get() - 1);
+    }
+}
+
+impl<T, A: Allocator> Drop for Slab<T, A> {
+    fn drop(&mut self) {
+        if self.capacity != 0 {
+            unsafe {
+                self.allocator.deallocate(
+                    self.slots.cast(),
+                    Layout::array::<Slot<T>>(self.capacity).unwrap(),
+                )
+            }
+        }
+    }
+}
+
+/// A buffer, which stores its value in a slot of a [`Slab`].
+///
+/// The slab is passed as external data. Freeing the buffer returns the slot to the slab.
+pub struct SlabBuffer<'a, T, A: Allocator = Global> {
+    slot: NonNull<Slot<T>>,
+    _slab: PhantomData<&'a Slab<T, A>>,
+}
+
+impl<'a, T, A: Allocator> SlabBuffer<'a, T, A> {
+    /// Takes a free slot of the slab.
+    ///
+    /// Returns an error if all slots are occupied.
+    pub fn new_in(slab: &'a Slab<T, A>) -> Result<Self, AllocError> {
+        Ok(Self {
+            slot: slab.take_slot().ok_or(AllocError)?,
+            _slab: PhantomData,
+        })
+    }
+}
+
+impl<'a, T, A: Allocator> ReadBuffer<T> for SlabBuffer<'a, T, A> {
+    type ExternalData = &'a Slab<T, A>;
+
+    fn as_ptr(&self, _slab: &Self::ExternalData) -> *const T {
+        self.slot.as_ptr().cast()
+    }
+}
+
+impl<T, A: Allocator> Buffer<T> for SlabBuffer<'_, T, A> {
+    fn as_mut_ptr(&mut self, _slab: &Self::ExternalData) -> *mut T {
+        self.slot.as_ptr().cast()
+    }
+}
+
+impl<T, A: Allocator> UnmanagedBuffer<T> for SlabBuffer<'_, T, A> {
+    unsafe fn free_unchecked(&mut self, slab: &Self::ExternalData) {
+        slab.release_slot(self.slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Counting;
+    use alloc::{rc::Rc, vec::Vec};
+
+    #[test]
+    fn reuse_slots() {
+        let counting = Counting::default();
+        let slab = Slab::with_capacity_in(4, &counting).unwrap();
+        let value = Rc::new(());
+
+        let mut addresses = Vec::new();
+        for _ in 0..100 {
+            let boxes: Vec<_> = (0..4)
+                .map(|_| slab.try_new_box(Rc::clone(&value)).ok().unwrap())
+                .collect();
+            assert!(slab.try_new_box(Rc::clone(&value)).is_err());
+            assert_eq!(Rc::strong_count(&value), 5);
+
+            for boxed in &boxes {
+                let address: *const Rc<()> = &**boxed;
+                if !addresses.contains(&address) {
+                    addresses.push(address);
+                }
+            }
+            drop(boxes);
+            assert!(slab.is_empty());
+            assert_eq!(Rc::strong_count(&value), 1);
+        }
+
+        assert_eq!(addresses.len(), 4);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 0);
+        drop(slab);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn free_list_order() {
+        let slab = Slab::<u64>::with_capacity(3);
+        let a = slab.try_new_box(1).ok().unwrap();
+        let b = slab.try_new_box(2).ok().unwrap();
+        let b_address: *const u64 = &*b;
+        drop(b);
+
+        let c = slab.try_new_box(3).ok().unwrap();
+        assert!(ptr::eq(&*c, b_address));
+        assert_eq!(slab.len(), 2);
+        assert_eq!(*a + *c, 4);
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let counting = Counting::default();
+        let slab = Slab::with_capacity_in(0, &counting).unwrap();
+        assert_eq!(slab.try_new_box(5_u8).err(), Some(5));
+        drop(slab);
+        assert_eq!(counting.allocations.get(), 0);
+        assert_eq!(counting.deallocations.get(), 0);
+    }
+}
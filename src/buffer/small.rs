@@ -0,0 +1,236 @@
+use super::{
+    alloc::checked_grow_capacity,
+    AllocateBuffer,
+    AllocatedBuffer,
+    Buffer,
+    GrowableBuffer,
+    ReadBuffer,
+    UnmanagedBuffer,
+};
+use alloc::collections::TryReserveError;
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    mem,
+    ptr,
+};
+
+enum Storage<T, A: ?Sized, const N: usize> {
+    Inline([mem::MaybeUninit<T>; N]),
+    Heap(AllocatedBuffer<[T], A>),
+}
+
+/// A slice buffer, which stores up to `N` elements inline and moves them to the heap when it
+/// grows beyond that.
+///
+/// Small collections don't allocate at all, while large collections are not limited to a fixed
+/// capacity. Once the elements are moved to the heap, they stay there. The allocator is passed
+/// as external data.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::{alloc::Global, mem::MaybeUninit};
+/// use storages::buffer::{GrowableBuffer, SmallBuffer, UnmanagedBuffer};
+///
+/// let mut buffer = SmallBuffer::<u32, Global, 4>::new();
+/// buffer.try_reserve(&Global, 0, 4)?;
+/// assert!(buffer.is_inline());
+///
+/// buffer.try_reserve(&Global, 4, 1)?;
+/// assert!(!buffer.is_inline());
+/// assert!(buffer.capacity() >= 5);
+///
+/// UnmanagedBuffer::<[MaybeUninit<u32>]>::free(buffer, &Global);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct SmallBuffer<T, A: ?Sized, const N: usize> {
+    storage: Storage<T, A, N>,
+}
+
+impl<T, A: ?Sized, const N: usize> SmallBuffer<T, A, N> {
+    /// Creates a buffer, which stores its elements inline.
+    pub fn new() -> Self {
+        Self {
+            storage: Storage::Inline(mem::MaybeUninit::uninit_array()),
+        }
+    }
+
+    /// Returns `true` if the elements are stored inline.
+    pub fn is_inline(&self) -> bool {
+        matches!(self.storage, Storage::Inline(_))
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const N: usize> SmallBuffer<T, A, N> {
+    /// Returns the number of elements the buffer can hold.
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline(_) => N,
+            Storage::Heap(buffer) => buffer.capacity(),
+        }
+    }
+}
+
+impl<T, A: ?Sized, const N: usize> Default for SmallBuffer<T, A, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const N: usize> ReadBuffer<[mem::MaybeUninit<T>]>
+    for SmallBuffer<T, A, N>
+{
+    type ExternalData = A;
+
+    fn as_ptr(&self, allocator: &Self::ExternalData) -> *const [mem::MaybeUninit<T>] {
+        match &self.storage {
+            Storage::Inline(elements) => &elements[..],
+            Storage::Heap(buffer) => ReadBuffer::<[mem::MaybeUninit<T>]>::as_ptr(buffer, allocator),
+        }
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const N: usize> Buffer<[mem::MaybeUninit<T>]>
+    for SmallBuffer<T, A, N>
+{
+    fn as_mut_ptr(&mut self, allocator: &Self::ExternalData) -> *mut [mem::MaybeUninit<T>] {
+        match &mut self.storage {
+            Storage::Inline(elements) => &mut elements[..],
+            Storage::Heap(buffer) => Buffer::<[mem::MaybeUninit<T>]>::as_mut_ptr(buffer, allocator),
+        }
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const N: usize> ReadBuffer<[T]> for SmallBuffer<T, A, N> {
+    type ExternalData = A;
+
+    fn as_ptr(&self, allocator: &Self::ExternalData) -> *const [T] {
+        ReadBuffer::<[mem::MaybeUninit<T>]>::as_ptr(self, allocator) as *const [T]
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const N: usize> Buffer<[T]> for SmallBuffer<T, A, N> {
+    fn as_mut_ptr(&mut self, allocator: &Self::ExternalData) -> *mut [T] {
+        Buffer::<[mem::MaybeUninit<T>]>::as_mut_ptr(self, allocator) as *mut [T]
+    }
+}
+
+/// Frees the heap memory, if the elements were moved to the heap.
+impl<T, A: ?Sized + Allocator, const N: usize> UnmanagedBuffer<[T]> for SmallBuffer<T, A, N> {
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        if let Storage::Heap(buffer) = &mut self.storage {
+            UnmanagedBuffer::<[T]>::free_unchecked(buffer, allocator);
+        }
+    }
+}
+
+impl<T, A: ?Sized + Allocator, const N: usize> UnmanagedBuffer<[mem::MaybeUninit<T>]>
+    for SmallBuffer<T, A, N>
+{
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        UnmanagedBuffer::<[T]>::free_unchecked(self, allocator)
+    }
+}
+
+/// Stores the elements inline, if `len` doesn't exceed `N`.
+impl<T, A: Allocator, const N: usize> AllocateBuffer<[mem::MaybeUninit<T>]>
+    for SmallBuffer<T, A, N>
+{
+    fn allocate(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        if len <= N {
+            return Ok(Self::new());
+        }
+        Ok(Self {
+            storage: Storage::Heap(AllocatedBuffer::new_slice(allocator, len)?),
+        })
+    }
+}
+
+/// Moves the elements to the heap, if `len + additional` exceeds `N`.
+impl<T, A: Allocator, const N: usize> GrowableBuffer<[mem::MaybeUninit<T>]>
+    for SmallBuffer<T, A, N>
+{
+    #[allow(clippy::map_err_ignore)]
+    fn try_reserve(
+        &mut self,
+        allocator: &A,
+        len: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let elements = match &mut self.storage {
+            Storage::Heap(buffer) => return buffer.try_reserve(allocator, len, additional),
+            Storage::Inline(elements) => elements,
+        };
+        let required = len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        if required <= N {
+            return Ok(());
+        }
+        debug_assert!(len <= N, "more elements than inline capacity");
+
+        let capacity = checked_grow_capacity(N, required)?;
+        let layout = Layout::array::<T>(capacity).map_err(|_| TryReserveError::CapacityOverflow)?;
+        let mut buffer = AllocatedBuffer::new_slice(allocator, capacity).map_err(|_| {
+            TryReserveError::AllocError {
+                layout,
+                non_exhaustive: (),
+            }
+        })?;
+        unsafe {
+            let dst: *mut [T] = buffer.as_mut_ptr(allocator);
+            ptr::copy_nonoverlapping(<[_]>::as_ptr(elements).cast::<T>(), dst.as_mut_ptr(), len);
+        }
+        self.storage = Storage::Heap(buffer);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Counting;
+
+    #[test]
+    fn spill_to_heap() {
+        let counting = Counting::default();
+        let mut buffer = SmallBuffer::<u32, _, 2>::new();
+        unsafe {
+            let elements: *mut [u32] = buffer.as_mut_ptr(&counting);
+            elements.as_mut_ptr().write(1);
+            elements.as_mut_ptr().add(1).write(2);
+        }
+        buffer.try_reserve(&counting, 2, 0).unwrap();
+        assert!(buffer.is_inline());
+        assert_eq!(counting.allocations.get(), 0);
+
+        buffer.try_reserve(&counting, 2, 1).unwrap();
+        assert!(!buffer.is_inline());
+        assert_eq!(buffer.capacity(), 4);
+        let elements: *const [u32] = buffer.as_ptr(&counting);
+        assert_eq!(unsafe { &(*elements)[..2] }, [1, 2]);
+
+        buffer.try_reserve(&counting, 4, 1).unwrap();
+        assert_eq!(counting.grows.get(), 1);
+
+        UnmanagedBuffer::<[u32]>::free(buffer, &counting);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn allocate() {
+        let counting = Counting::default();
+        let inline = SmallBuffer::<u8, _, 4>::allocate(&counting, 4).unwrap();
+        assert!(inline.is_inline());
+        let slots: *const [mem::MaybeUninit<u8>] = inline.as_ptr(&counting);
+        assert_eq!(slots.len(), 4);
+
+        let heap = SmallBuffer::<u8, _, 4>::allocate(&counting, 5).unwrap();
+        assert!(heap.capacity() >= 5);
+        UnmanagedBuffer::<[u8]>::free(heap, &counting);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+}
@@ -0,0 +1,95 @@
+use super::{Buffer, ReadBuffer, UnmanagedBuffer};
+use core::cell::Cell;
+
+/// A buffer, which counts how often the wrapped buffer is accessed and freed.
+///
+/// This is a debugging aid and can wrap any other buffer.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::System;
+/// use storages::{
+///     boxed::RawBox,
+///     buffer::{AllocatedBuffer, StatsBuffer, UnmanagedBuffer},
+/// };
+///
+/// let buffer = StatsBuffer::new(AllocatedBuffer::new_in(&System)?);
+/// let mut five = RawBox::new_in(5, buffer, &System);
+///
+/// *five.as_mut(&System) += 1;
+/// assert_eq!(*five.as_ref(&System), 6);
+///
+/// let buffer = five.buffer_mut();
+/// assert_eq!(buffer.reads(), 1);
+/// assert_eq!(buffer.writes(), 2);
+///
+/// unsafe { buffer.free_unchecked(&System) };
+/// assert_eq!(buffer.frees(), 1);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub struct StatsBuffer<B> {
+    buffer: B,
+    reads: Cell<usize>,
+    writes: Cell<usize>,
+    frees: Cell<usize>,
+}
+
+impl<B> StatsBuffer<B> {
+    /// Wraps `buffer` with all counters set to zero.
+    pub const fn new(buffer: B) -> Self {
+        Self {
+            buffer,
+            reads: Cell::new(0),
+            writes: Cell::new(0),
+            frees: Cell::new(0),
+        }
+    }
+
+    /// Returns how often a shared pointer was requested.
+    pub fn reads(&self) -> usize {
+        self.reads.get()
+    }
+
+    /// Returns how often a unique pointer was requested.
+    pub fn writes(&self) -> usize {
+        self.writes.get()
+    }
+
+    /// Returns how often the buffer was freed.
+    pub fn frees(&self) -> usize {
+        self.frees.get()
+    }
+
+    /// Returns the wrapped buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<T: ?Sized, B: ReadBuffer<T>> ReadBuffer<T> for StatsBuffer<B> {
+    type ExternalData = B::ExternalData;
+
+    const IS_MANAGED: bool = B::IS_MANAGED;
+
+    fn as_ptr(&self, data: &Self::ExternalData) -> *const T {
+        self.reads.set(self.reads.get() + 1);
+        self.buffer.as_ptr(data)
+    }
+}
+
+impl<T: ?Sized, B: Buffer<T>> Buffer<T> for StatsBuffer<B> {
+    fn as_mut_ptr(&mut self, data: &Self::ExternalData) -> *mut T {
+        self.writes.set(self.writes.get() + 1);
+        self.buffer.as_mut_ptr(data)
+    }
+}
+
+impl<T: ?Sized, B: UnmanagedBuffer<T>> UnmanagedBuffer<T> for StatsBuffer<B> {
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        self.frees.set(self.frees.get() + 1);
+        self.buffer.free_unchecked(allocator)
+    }
+}
@@ -0,0 +1,116 @@
+use super::{Buffer, ReadBuffer, UnmanagedBuffer};
+use core::marker::PhantomData;
+
+/// Determines what happens to an unmanaged buffer of a box, when the box is dropped.
+///
+/// Use it with [`StrategyBuffer`] to customize the drop glue of a box.
+pub trait DropStrategy {
+    /// Whether the boxed value is dropped.
+    const DROP_VALUE: bool;
+
+    /// Whether the buffer is freed.
+    const FREE: bool;
+}
+
+/// Drops the boxed value and frees the buffer. This is the default for unmanaged buffers.
+pub struct DropValueAndFree;
+
+/// Drops the boxed value, but keeps the buffer, e.g. for arenas, which release their memory at
+/// once.
+pub struct DropValueOnly;
+
+/// Frees the buffer without dropping the boxed value.
+pub struct FreeOnly;
+
+/// Neither drops the boxed value nor frees the buffer.
+pub struct Neither;
+
+impl DropStrategy for DropValueAndFree {
+    const DROP_VALUE: bool = true;
+    const FREE: bool = true;
+}
+
+impl DropStrategy for DropValueOnly {
+    const DROP_VALUE: bool = true;
+    const FREE: bool = false;
+}
+
+impl DropStrategy for FreeOnly {
+    const DROP_VALUE: bool = false;
+    const FREE: bool = true;
+}
+
+impl DropStrategy for Neither {
+    const DROP_VALUE: bool = false;
+    const FREE: bool = false;
+}
+
+/// A buffer, which customizes how a box drops the wrapped unmanaged buffer.
+///
+/// Freeing the buffer explicitly through [`UnmanagedBuffer`] is not affected by the strategy.
+/// Managed buffers release their contents themselves, so the strategy has no effect on them.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// # #[cfg(feature = "global")] {
+/// use std::{alloc::Global, rc::Rc};
+/// use storages::{
+///     boxed::Box,
+///     buffer::{AllocatedBuffer, FreeOnly, StrategyBuffer},
+/// };
+///
+/// let value = Rc::new(());
+/// let buffer = StrategyBuffer::<_, FreeOnly>::new(AllocatedBuffer::new()?);
+/// let boxed = Box::new_in(Rc::clone(&value), buffer, Global);
+///
+/// drop(boxed);
+/// assert_eq!(Rc::strong_count(&value), 2);
+/// # }
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub struct StrategyBuffer<B, S: DropStrategy> {
+    buffer: B,
+    _strategy: PhantomData<S>,
+}
+
+impl<B, S: DropStrategy> StrategyBuffer<B, S> {
+    /// Wraps `buffer`, which is dropped according to `S`.
+    pub const fn new(buffer: B) -> Self {
+        Self {
+            buffer,
+            _strategy: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped buffer.
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+}
+
+impl<T: ?Sized, B: ReadBuffer<T>, S: DropStrategy> ReadBuffer<T> for StrategyBuffer<B, S> {
+    type ExternalData = B::ExternalData;
+
+    const IS_MANAGED: bool = B::IS_MANAGED;
+
+    fn as_ptr(&self, data: &Self::ExternalData) -> *const T {
+        self.buffer.as_ptr(data)
+    }
+}
+
+impl<T: ?Sized, B: Buffer<T>, S: DropStrategy> Buffer<T> for StrategyBuffer<B, S> {
+    fn as_mut_ptr(&mut self, data: &Self::ExternalData) -> *mut T {
+        self.buffer.as_mut_ptr(data)
+    }
+}
+
+impl<T: ?Sized, B: UnmanagedBuffer<T>, S: DropStrategy> UnmanagedBuffer<T>
+    for StrategyBuffer<B, S>
+{
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        self.buffer.free_unchecked(allocator)
+    }
+}
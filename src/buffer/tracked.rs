@@ -0,0 +1,178 @@
+use super::{AllocatedBuffer, Buffer, ReadBuffer, UnmanagedBuffer};
+use alloc::alloc::Global;
+use core::{
+    alloc::{AllocError, Allocator},
+    mem,
+    ptr,
+};
+
+/// An allocated slice buffer, which remembers the length requested from the allocator.
+///
+/// Allocators may return larger blocks than requested, e.g. when rounding up to a size class.
+/// [`AllocatedBuffer<[T]>`] uses the excess memory as capacity, but doesn't keep the requested
+/// length, as it's one pointer wide. This buffer stores the requested length next to the
+/// allocation, so the over-allocation can be inspected with [`allocation_info`]. The requested
+/// number of elements is exposed, until it's changed with [`set_len`].
+///
+/// [`AllocatedBuffer<[T]>`]: crate::buffer::AllocatedBuffer
+/// [`allocation_info`]: Self::allocation_info
+/// [`set_len`]: Self::set_len
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api, slice_ptr_len)]
+///
+/// use std::alloc::System;
+/// use storages::buffer::{ReadBuffer, TrackedBuffer, UnmanagedBuffer};
+///
+/// let buffer = TrackedBuffer::<u32, _>::new_slice(&System, 3)?;
+/// let (requested, capacity) = buffer.allocation_info();
+///
+/// assert_eq!(requested, 3);
+/// assert!(capacity >= requested);
+///
+/// let values: *const [u32] = buffer.as_ptr(&System);
+/// assert_eq!(values.len(), 3);
+///
+/// buffer.free(&System);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub struct TrackedBuffer<T, A: ?Sized = Global> {
+    buffer: AllocatedBuffer<[T], A>,
+    requested: usize,
+    len: usize,
+}
+
+impl<T, A: ?Sized + Allocator> TrackedBuffer<T, A> {
+    /// Allocates a buffer for at least `len` elements.
+    ///
+    /// See [`AllocatedBuffer::new_slice`].
+    pub fn new_slice(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        AllocatedBuffer::new_slice(allocator, len).map(|buffer| Self {
+            buffer,
+            requested: len,
+            len,
+        })
+    }
+
+    /// Allocates a buffer for at least `len` elements with the memory being filled with `0`
+    /// bytes.
+    ///
+    /// See [`AllocatedBuffer::new_slice_zeroed`].
+    pub fn new_slice_zeroed(allocator: &A, len: usize) -> Result<Self, AllocError> {
+        AllocatedBuffer::new_slice_zeroed(allocator, len).map(|buffer| Self {
+            buffer,
+            requested: len,
+            len,
+        })
+    }
+
+    /// Returns the number of elements requested from the allocator and the capacity it actually
+    /// provided.
+    ///
+    /// The difference is excess capacity, which the allocator handed out in addition.
+    pub fn allocation_info(&self) -> (usize, usize) {
+        (self.requested, self.buffer.capacity())
+    }
+
+    /// Changes the number of exposed elements to `len`.
+    ///
+    /// Unlike the requested length, `len` may use the excess capacity of the allocation.
+    ///
+    /// # Safety
+    ///
+    /// When viewed as `[T]`, the first `len` elements must be initialized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is greater than the capacity.
+    pub unsafe fn set_len(&mut self, len: usize) {
+        let capacity = self.buffer.capacity();
+        assert!(
+            len <= capacity,
+            "`new_len` (is {}) should be <= capacity (is {})",
+            len,
+            capacity
+        );
+        self.len = len;
+    }
+}
+
+impl<T, A: ?Sized + Allocator> ReadBuffer<[T]> for TrackedBuffer<T, A> {
+    type ExternalData = A;
+
+    fn as_ptr(&self, allocator: &Self::ExternalData) -> *const [T] {
+        let ptr: *const [T] = self.buffer.as_ptr(allocator);
+        ptr::slice_from_raw_parts(ptr.cast(), self.len)
+    }
+}
+
+impl<T, A: ?Sized + Allocator> Buffer<[T]> for TrackedBuffer<T, A> {
+    fn as_mut_ptr(&mut self, allocator: &Self::ExternalData) -> *mut [T] {
+        let ptr: *mut [T] = self.buffer.as_mut_ptr(allocator);
+        ptr::slice_from_raw_parts_mut(ptr.cast(), self.len)
+    }
+}
+
+impl<T, A: ?Sized + Allocator> ReadBuffer<[mem::MaybeUninit<T>]> for TrackedBuffer<T, A> {
+    type ExternalData = A;
+
+    // only the initialized buffer is an `UnmanagedBuffer`, so the default can't be derived
+    const IS_MANAGED: bool = false;
+
+    fn as_ptr(&self, allocator: &Self::ExternalData) -> *const [mem::MaybeUninit<T>] {
+        let ptr: *const [T] = self.buffer.as_ptr(allocator);
+        ptr::slice_from_raw_parts(ptr.cast(), self.len)
+    }
+}
+
+impl<T, A: ?Sized + Allocator> Buffer<[mem::MaybeUninit<T>]> for TrackedBuffer<T, A> {
+    fn as_mut_ptr(&mut self, allocator: &Self::ExternalData) -> *mut [mem::MaybeUninit<T>] {
+        let ptr: *mut [T] = self.buffer.as_mut_ptr(allocator);
+        ptr::slice_from_raw_parts_mut(ptr.cast(), self.len)
+    }
+}
+
+impl<T, A: Allocator> UnmanagedBuffer<[T]> for TrackedBuffer<T, A> {
+    unsafe fn free_unchecked(&mut self, allocator: &Self::ExternalData) {
+        // the whole capacity fits the allocation, so it's freed with the returned size
+        UnmanagedBuffer::<[T]>::free_unchecked(&mut self.buffer, allocator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        boxed::Box,
+        testing::{Counting, Rounding},
+    };
+
+    #[test]
+    fn over_allocation() {
+        let rounding = Rounding::default();
+        let buffer = TrackedBuffer::<u32, _>::new_slice(&rounding, 3).unwrap();
+        let (requested, actual) = buffer.allocation_info();
+        assert_eq!(requested, 3);
+        assert_eq!(actual, 16);
+        assert!(actual >= requested);
+
+        let values: *const [u32] = buffer.as_ptr(&rounding);
+        assert_eq!(values.len(), 3);
+        buffer.free(&rounding);
+    }
+
+    #[test]
+    fn boxed() {
+        let counting = Counting::default();
+        let buffer = TrackedBuffer::new_slice_zeroed(&&counting, 5).unwrap();
+        assert_eq!(buffer.allocation_info(), (5, 5));
+        let values = unsafe { Box::<[u32], _>::from_buffer(buffer, &counting) };
+        assert_eq!(*values, [0; 5]);
+
+        drop(values);
+        assert_eq!(counting.deallocations.get(), 1);
+        assert_eq!(counting.bytes.get(), 0);
+    }
+}
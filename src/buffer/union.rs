@@ -0,0 +1,118 @@
+/// Declares a buffer, which can hold a value of any of the listed types inline.
+///
+/// The generated struct is sized and aligned for the largest of the listed types and implements
+/// [`Buffer<T>`] and [`Buffer<MaybeUninit<T>>`] with `ExternalData = ()` for each of them. This
+/// allows storing one of several types without allocating, e.g. for enums of boxes. Each type may
+/// only be listed once.
+///
+/// The buffer doesn't track which type is stored, so it's up to the user to only read the type,
+/// which was written last.
+///
+/// [`Buffer<T>`]: crate::buffer::Buffer
+/// [`Buffer<MaybeUninit<T>>`]: crate::buffer::Buffer
+///
+/// # Examples
+///
+/// ```
+/// use storages::{
+///     boxed::Box,
+///     buffer::{Buffer, ReadBuffer},
+///     union_buffer,
+/// };
+///
+/// union_buffer! {
+///     /// Storage for either a `u8` or a `u64`.
+///     pub struct Small { u8, u64 }
+/// }
+///
+/// assert_eq!(std::mem::size_of::<Small>(), 8);
+/// assert_eq!(std::mem::align_of::<Small>(), 8);
+///
+/// let byte: Box<u8, Small> = Box::new_in(5, Small::new(), ());
+/// assert_eq!(*byte, 5);
+///
+/// let mut buffer = Small::new();
+/// let ptr: *mut u8 = buffer.as_mut_ptr(&());
+/// unsafe { ptr.write(5) };
+/// let ptr: *const u8 = buffer.as_ptr(&());
+/// assert_eq!(unsafe { ptr.read() }, 5);
+///
+/// // Reuse the same storage for a larger type
+/// let ptr: *mut u64 = buffer.as_mut_ptr(&());
+/// unsafe { ptr.write(u64::MAX) };
+/// let ptr: *const u64 = buffer.as_ptr(&());
+/// assert_eq!(unsafe { ptr.read() }, u64::MAX);
+/// ```
+#[macro_export]
+macro_rules! union_buffer {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident { $($ty:ty),+ $(,)? }) => {
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name {
+            _align: ($([$ty; 0],)+),
+            storage: ::core::mem::MaybeUninit<
+                [u8; $crate::buffer::__max_size(&[$(::core::mem::size_of::<$ty>()),+])]
+            >,
+        }
+
+        impl $name {
+            /// Creates an uninitialized buffer.
+            $vis const fn new() -> Self {
+                Self {
+                    _align: ($([] as [$ty; 0],)+),
+                    storage: ::core::mem::MaybeUninit::uninit(),
+                }
+            }
+        }
+
+        impl ::core::default::Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        $(
+            impl $crate::buffer::ReadBuffer<$ty> for $name {
+                type ExternalData = ();
+
+                fn as_ptr(&self, _data: &Self::ExternalData) -> *const $ty {
+                    self.storage.as_ptr().cast()
+                }
+            }
+
+            impl $crate::buffer::Buffer<$ty> for $name {
+                fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut $ty {
+                    self.storage.as_mut_ptr().cast()
+                }
+            }
+
+            impl $crate::buffer::ReadBuffer<::core::mem::MaybeUninit<$ty>> for $name {
+                type ExternalData = ();
+
+                fn as_ptr(&self, _data: &Self::ExternalData) -> *const ::core::mem::MaybeUninit<$ty> {
+                    self.storage.as_ptr().cast()
+                }
+            }
+
+            impl $crate::buffer::Buffer<::core::mem::MaybeUninit<$ty>> for $name {
+                fn as_mut_ptr(&mut self, _data: &Self::ExternalData) -> *mut ::core::mem::MaybeUninit<$ty> {
+                    self.storage.as_mut_ptr().cast()
+                }
+            }
+        )+
+    };
+}
+
+/// Returns the largest of `sizes`, used by [`union_buffer!`] to size the storage.
+#[doc(hidden)]
+pub const fn __max_size(sizes: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < sizes.len() {
+        if sizes[i] > max {
+            max = sizes[i];
+        }
+        i += 1;
+    }
+    max
+}
@@ -7,14 +7,20 @@
     specialization,
     coerce_unsized,
     unsize,
-    min_const_generics
+    min_const_generics,
+    try_reserve,
+    container_error_extra,
+    const_panic,
+    const_fn
 )]
 // convenient features
 #![feature(
     nonnull_slice_from_raw_parts,
     int_bits_const,
     slice_ptr_len,
-    slice_ptr_get
+    slice_ptr_get,
+    slice_fill,
+    maybe_uninit_uninit_array
 )]
 #![allow(incomplete_features)]
 #![allow(
@@ -56,6 +62,16 @@
 )]
 
 extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
 
 pub mod boxed;
 pub mod buffer;
+pub mod ring;
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+pub mod sync;
+pub mod vec;
+
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,493 @@
+//! Ring buffers and double-ended queues on top of boxed slices.
+
+use crate::{
+    boxed::{handle_reserve, Box},
+    buffer::{AllocatedBuffer, Buffer, ReadBuffer, TakeInitialized},
+};
+use core::{alloc::Allocator, mem, ptr};
+
+/// A fixed-capacity queue, which overwrites the oldest element when it's full.
+///
+/// The ring buffer is a [`Deque`], which pops the front element to make room. Like the deque, any
+/// buffer providing a [`Buffer<[MaybeUninit<T>]>`] can be used as backing, e.g. an
+/// [`AllocatedBuffer`] or an array of uninitialized elements stored inline. The ring buffer tracks
+/// the initialized elements itself and drops them when it's dropped.
+///
+/// [`Buffer<[MaybeUninit<T>]>`]: crate::buffer::Buffer
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::ring::RingBuffer;
+///
+/// let mut values = RingBuffer::with_capacity(2);
+/// values.push_back(1);
+/// values.push_back(2);
+/// values.push_back(3);
+///
+/// assert_eq!(values.pop_front(), Some(2));
+/// assert_eq!(values.pop_front(), Some(3));
+/// assert_eq!(values.pop_front(), None);
+/// # }
+/// ```
+///
+/// Storing the elements inline:
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use storages::ring::RingBuffer;
+///
+/// let mut values = RingBuffer::new_in([MaybeUninit::<u32>::uninit(); 4], ());
+/// values.push_back(1);
+///
+/// assert_eq!(values.front(), Some(&1));
+/// assert_eq!(values.capacity(), 4);
+/// ```
+pub struct RingBuffer<
+    T,
+    B = AllocatedBuffer<[T]>,
+    D = <B as ReadBuffer<[mem::MaybeUninit<T>]>>::ExternalData,
+> where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    values: Deque<T, B, D>,
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> RingBuffer<T> {
+    /// Creates an empty ring buffer for `capacity` elements on the global heap.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Deque::with_capacity(capacity),
+        }
+    }
+}
+
+impl<T, B, D> RingBuffer<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    /// Creates a ring buffer, which uses all elements of `buffer` as capacity.
+    ///
+    /// Elements the buffer already contains, like those of a non-empty [`InlineBuffer`], become
+    /// the first elements from the front.
+    ///
+    /// [`InlineBuffer`]: crate::buffer::InlineBuffer
+    pub fn new_in(buffer: B, data: D) -> Self {
+        Self {
+            values: Deque::new_in(buffer, data),
+        }
+    }
+
+    /// Returns the number of elements the ring buffer can hold.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Returns the number of elements in the ring buffer.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the ring buffer contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if the ring buffer holds [`capacity`] elements.
+    ///
+    /// [`capacity`]: Self::capacity
+    pub fn is_full(&self) -> bool {
+        self.values.is_full()
+    }
+
+    /// Returns a reference to the oldest element, or `None` if the ring buffer is empty.
+    pub fn front(&self) -> Option<&T> {
+        self.values.front()
+    }
+
+    /// Appends an element to the back of the ring buffer.
+    ///
+    /// If the ring buffer is full, the oldest element is dropped and replaced by `value`.
+    pub fn push_back(&mut self, value: T) {
+        if self.values.is_full() {
+            drop(self.values.pop_front());
+        }
+        // without capacity, `value` is dropped right away
+        drop(self.values.try_push_back(value));
+    }
+
+    /// Removes the oldest element and returns it, or `None` if the ring buffer is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.values.pop_front()
+    }
+}
+
+/// A double-ended queue, which stores its elements in a boxed slice.
+///
+/// The elements wrap around the end of the slice, so any buffer providing a
+/// [`Buffer<[MaybeUninit<T>]>`] can be used as backing. Instead of overwriting elements, pushing
+/// to a full deque fails with [`try_push_back`] and [`try_push_front`]. Deques backed by an
+/// [`AllocatedBuffer`] can grow with [`push_back`] and [`push_front`] instead.
+///
+/// [`Buffer<[MaybeUninit<T>]>`]: crate::buffer::Buffer
+/// [`try_push_back`]: Self::try_push_back
+/// [`try_push_front`]: Self::try_push_front
+/// [`push_back`]: Self::push_back
+/// [`push_front`]: Self::push_front
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::ring::Deque;
+///
+/// let mut values = Deque::with_capacity(1);
+/// values.push_back(2);
+/// values.push_back(3);
+/// values.push_front(1);
+///
+/// assert_eq!(values.pop_front(), Some(1));
+/// assert_eq!(values.pop_back(), Some(3));
+/// assert_eq!(values.pop_back(), Some(2));
+/// assert_eq!(values.pop_front(), None);
+/// # }
+/// ```
+///
+/// Storing the elements inline:
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use storages::ring::Deque;
+///
+/// let mut values = Deque::new_in([MaybeUninit::<u32>::uninit(); 2], ());
+///
+/// assert_eq!(values.try_push_back(1), Ok(()));
+/// assert_eq!(values.try_push_front(0), Ok(()));
+/// assert_eq!(values.try_push_back(2), Err(2));
+/// assert_eq!(values.back(), Some(&1));
+/// ```
+pub struct Deque<
+    T,
+    B = AllocatedBuffer<[T]>,
+    D = <B as ReadBuffer<[mem::MaybeUninit<T>]>>::ExternalData,
+> where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    slots: Box<[mem::MaybeUninit<T>], B, D>,
+    head: usize,
+    len: usize,
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> Deque<T> {
+    /// Creates an empty deque for `capacity` elements on the global heap.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_box(Box::<[T]>::new_uninit_slice(capacity))
+    }
+}
+
+impl<T, B, D> Deque<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    /// Creates a deque, which uses all elements of `buffer` as capacity.
+    ///
+    /// Elements the buffer already contains, like those of a non-empty [`InlineBuffer`], become
+    /// the first elements from the front.
+    ///
+    /// [`InlineBuffer`]: crate::buffer::InlineBuffer
+    pub fn new_in(mut buffer: B, data: D) -> Self {
+        let len = buffer.take_initialized();
+        let mut values = Self::from_box(unsafe { Box::from_buffer(buffer, data) });
+        values.len = len;
+        values
+    }
+
+    fn from_box(slots: Box<[mem::MaybeUninit<T>], B, D>) -> Self {
+        Self {
+            slots,
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of elements the deque can hold without growing.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the deque holds [`capacity`] elements.
+    ///
+    /// [`capacity`]: Self::capacity
+    pub fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    /// Maps the logical `index` to the index of its slot.
+    fn slot(&self, index: usize) -> usize {
+        let slot = self.head + index;
+        if slot >= self.capacity() {
+            slot - self.capacity()
+        } else {
+            slot
+        }
+    }
+
+    /// Returns a reference to the first element, or `None` if the deque is empty.
+    pub fn front(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe { Some(&*self.slots[self.head].as_ptr()) }
+    }
+
+    /// Returns a reference to the last element, or `None` if the deque is empty.
+    pub fn back(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe { Some(&*self.slots[self.slot(self.len - 1)].as_ptr()) }
+    }
+
+    /// Appends an element to the back of the deque.
+    ///
+    /// If the deque is full, the element is returned in `Err`.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        let tail = self.slot(self.len);
+        self.slots[tail] = mem::MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Prepends an element to the front of the deque.
+    ///
+    /// If the deque is full, the element is returned in `Err`.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.head = self.slot(self.capacity() - 1);
+        self.slots[self.head] = mem::MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the first element and returns it, or `None` if the deque is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = unsafe { self.slots[self.head].as_ptr().read() };
+        self.head = self.slot(1);
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes the last element and returns it, or `None` if the deque is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.slots[self.slot(self.len)].as_ptr().read() })
+    }
+}
+
+impl<T, A: Allocator> Deque<T, AllocatedBuffer<[T], A>, A> {
+    /// Grows the buffer and moves the wrapped-around front elements to its end.
+    fn grow(&mut self) {
+        let old_capacity = self.capacity();
+        handle_reserve(self.slots.try_reserve(1));
+        let front_len = old_capacity - self.head;
+        if front_len < self.len {
+            let new_head = self.capacity() - front_len;
+            unsafe {
+                let slots = self.slots.as_mut_ptr();
+                ptr::copy(slots.add(self.head), slots.add(new_head), front_len);
+            }
+            self.head = new_head;
+        }
+    }
+
+    /// Appends an element to the back of the deque, growing the buffer if needed.
+    pub fn push_back(&mut self, value: T) {
+        if self.is_full() {
+            self.grow();
+        }
+        let pushed = self.try_push_back(value);
+        debug_assert!(pushed.is_ok(), "deque is full after growing");
+    }
+
+    /// Prepends an element to the front of the deque, growing the buffer if needed.
+    pub fn push_front(&mut self, value: T) {
+        if self.is_full() {
+            self.grow();
+        }
+        let pushed = self.try_push_front(value);
+        debug_assert!(pushed.is_ok(), "deque is full after growing");
+    }
+}
+
+impl<T, B, D> Drop for Deque<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Counting;
+    use alloc::rc::Rc;
+
+    #[test]
+    fn fill_wrap_drain() {
+        let counting = Counting::default();
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice_exact(&&counting, 3).unwrap();
+        let mut values = RingBuffer::new_in(buffer, &counting);
+
+        for value in 0..3 {
+            values.push_back(value);
+        }
+        assert!(values.is_full());
+        assert_eq!(values.pop_front(), Some(0));
+
+        values.push_back(3);
+        values.push_back(4);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values.front(), Some(&2));
+
+        assert_eq!(values.pop_front(), Some(2));
+        assert_eq!(values.pop_front(), Some(3));
+        assert_eq!(values.pop_front(), Some(4));
+        assert_eq!(values.pop_front(), None);
+
+        drop(values);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn inline_drops_overwritten() {
+        let value = Rc::new(());
+        let buffer = mem::MaybeUninit::<Rc<()>>::uninit_array::<2>();
+        let mut values = RingBuffer::new_in(buffer, ());
+
+        for _ in 0..5 {
+            values.push_back(Rc::clone(&value));
+        }
+        assert_eq!(values.len(), 2);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        drop(values.pop_front());
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[cfg(feature = "global")]
+    #[test]
+    fn zero_capacity() {
+        let value = Rc::new(());
+        let mut values = RingBuffer::with_capacity(0);
+        values.push_back(Rc::clone(&value));
+
+        assert!(values.is_empty());
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn deque_wraps_in_both_directions() {
+        let value = Rc::new(());
+        let buffer = mem::MaybeUninit::<(i32, Rc<()>)>::uninit_array::<3>();
+        let mut values = Deque::new_in(buffer, ());
+        values.try_push_back((0, Rc::clone(&value))).unwrap();
+
+        // rotate forward, the head wraps around the end of the buffer
+        for i in 1..10 {
+            values.try_push_back((i, Rc::clone(&value))).unwrap();
+            assert_eq!(values.pop_front().map(|front| front.0), Some(i - 1));
+        }
+        // rotate backward, the head wraps around the start of the buffer
+        for i in (0..9).rev() {
+            values.try_push_front((i, Rc::clone(&value))).unwrap();
+            assert_eq!(values.pop_back().map(|back| back.0), Some(i + 1));
+        }
+
+        values.try_push_front((-1, Rc::clone(&value))).unwrap();
+        values.try_push_back((1, Rc::clone(&value))).unwrap();
+        assert!(values.is_full());
+        assert_eq!(
+            values
+                .try_push_back((2, Rc::clone(&value)))
+                .map_err(|v| v.0),
+            Err(2)
+        );
+        assert_eq!(
+            values
+                .try_push_front((-2, Rc::clone(&value)))
+                .map_err(|v| v.0),
+            Err(-2)
+        );
+        assert_eq!(values.front().map(|front| front.0), Some(-1));
+        assert_eq!(values.back().map(|back| back.0), Some(1));
+        assert_eq!(Rc::strong_count(&value), 4);
+
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn deque_grows() {
+        let counting = Counting::default();
+        let buffer = AllocatedBuffer::<[u32], _>::new_slice_exact(&&counting, 4).unwrap();
+        let mut values = Deque::new_in(buffer, &counting);
+
+        // wrap the elements around the end before growing
+        for value in 0..3 {
+            values.push_back(value);
+        }
+        for value in (-3..0).rev() {
+            values.push_front(value as u32);
+        }
+        for value in 3..20 {
+            values.push_back(value);
+        }
+        for value in (-10..-3).rev() {
+            values.push_front(value as u32);
+        }
+        assert_eq!(values.len(), 30);
+        assert!(values.capacity() >= 30);
+        assert!(counting.grows.get() > 0);
+
+        for value in -10..20 {
+            assert_eq!(values.pop_front(), Some(value as u32));
+        }
+        assert!(values.is_empty());
+
+        drop(values);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+}
@@ -0,0 +1,194 @@
+//! Thread-safe reference-counted slices.
+
+use crate::buffer::{Buffer, LayoutBuffer, ReadBuffer, UnmanagedBuffer};
+use alloc::alloc::{handle_alloc_error, Global};
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    ops::Deref,
+    ptr::{self, NonNull},
+    sync::atomic::{self, AtomicUsize, Ordering},
+};
+
+/// A thread-safe reference-counted slice.
+///
+/// The reference count and the elements are stored in a single allocation on the global heap.
+/// Cloning an `Arc` only increments the reference count, the elements are dropped and the memory
+/// is freed when the last `Arc` is dropped. As there are no weak references, the count is the
+/// only header.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use storages::sync::Arc;
+///
+/// let values = Arc::from_slice(&[1, 2, 3]);
+/// let shared = Arc::clone(&values);
+///
+/// let sum = thread::spawn(move || shared.iter().sum::<u32>())
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(sum, 6);
+/// assert_eq!(Arc::strong_count(&values), 1);
+/// ```
+pub struct Arc<T: ?Sized> {
+    buffer: LayoutBuffer<Global>,
+    value: NonNull<T>,
+    _owned: PhantomData<T>,
+}
+
+unsafe impl<T: ?Sized + Send + Sync> Send for Arc<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for Arc<T> {}
+
+/// Drops the cloned elements and frees the buffer, if cloning an element panics.
+struct CloneGuard<T> {
+    buffer: Option<LayoutBuffer<Global>>,
+    elements: *mut T,
+    len: usize,
+}
+
+impl<T> Drop for CloneGuard<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.elements, self.len));
+            if let Some(buffer) = self.buffer.take() {
+                buffer.free(&Global);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Arc<[T]> {
+    /// Clones the elements of `src` into a new reference-counted slice.
+    pub fn from_slice(src: &[T]) -> Self {
+        let (layout, offset) = Layout::array::<T>(src.len())
+            .and_then(|elements| Layout::new::<AtomicUsize>().extend(elements))
+            .expect("capacity overflow");
+        let layout = layout.pad_to_align();
+        let mut buffer =
+            LayoutBuffer::new_in(layout, &Global).unwrap_or_else(|_| handle_alloc_error(layout));
+        let base: *mut [u8] = buffer.as_mut_ptr(&Global);
+        let base = base.as_mut_ptr();
+        unsafe { base.cast::<AtomicUsize>().write(AtomicUsize::new(1)) };
+
+        let mut guard = CloneGuard {
+            buffer: Some(buffer),
+            elements: unsafe { base.add(offset).cast::<T>() },
+            len: 0,
+        };
+        for value in src {
+            unsafe { guard.elements.add(guard.len).write(value.clone()) };
+            guard.len += 1;
+        }
+        guard.len = 0;
+
+        let value = ptr::slice_from_raw_parts_mut(guard.elements, src.len());
+        Self {
+            buffer: guard.buffer.take().unwrap(),
+            value: unsafe { NonNull::new_unchecked(value) },
+            _owned: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Arc<T> {
+    fn count(&self) -> &AtomicUsize {
+        let base: *const [u8] = self.buffer.as_ptr(&Global);
+        unsafe { &*base.as_ptr().cast::<AtomicUsize>() }
+    }
+
+    /// Returns the number of `Arc`s pointing to this allocation.
+    pub fn strong_count(this: &Self) -> usize {
+        this.count().load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if both `Arc`s point to the same allocation.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.value.cast::<u8>() == other.value.cast::<u8>()
+    }
+}
+
+impl<T: ?Sized> Clone for Arc<T> {
+    fn clone(&self) -> Self {
+        let old = self.count().fetch_add(1, Ordering::Relaxed);
+        assert!(old <= isize::MAX as usize, "reference count overflow");
+        Self {
+            buffer: unsafe { ptr::read(&self.buffer) },
+            value: self.value,
+            _owned: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for Arc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.value.as_ref() }
+    }
+}
+
+impl<T: ?Sized> Drop for Arc<T> {
+    fn drop(&mut self) {
+        if self.count().fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        atomic::fence(Ordering::Acquire);
+        unsafe {
+            ptr::drop_in_place(self.value.as_ptr());
+            self.buffer.free_unchecked(&Global);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use std::thread;
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct Counted(u64);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn share_across_threads() {
+        let values = Arc::from_slice(&[Counted(1), Counted(2), Counted(3)]);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let values = Arc::clone(&values);
+                thread::spawn(move || values.iter().map(|value| value.0).sum::<u64>())
+            })
+            .collect();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), 6);
+        }
+        assert_eq!(Arc::strong_count(&values), 1);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 3);
+
+        drop(values);
+        assert_eq!(DROPS.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn empty_and_zero_sized() {
+        let empty = Arc::<[u64]>::from_slice(&[]);
+        assert!(empty.is_empty());
+
+        let units = Arc::from_slice(&[(), ()]);
+        let clone = Arc::clone(&units);
+        assert!(Arc::ptr_eq(&units, &clone));
+        assert_eq!(clone.len(), 2);
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::alloc::Global;
+use core::{
+    alloc::{AllocError, Allocator, Layout},
+    cell::Cell,
+    ptr::NonNull,
+};
+
+/// Allocator forwarding to `Global`, which counts the calls to its methods and the bytes currently
+/// allocated.
+#[derive(Default)]
+pub struct Counting {
+    pub allocations: Cell<usize>,
+    pub deallocations: Cell<usize>,
+    pub grows: Cell<usize>,
+    pub shrinks: Cell<usize>,
+    pub bytes: Cell<usize>,
+}
+
+impl Counting {
+    /// Counts a successful call in `counter` and replaces `old_size` allocated bytes by `new_size`.
+    fn record(
+        &self,
+        result: Result<NonNull<[u8]>, AllocError>,
+        counter: &Cell<usize>,
+        old_size: usize,
+        new_size: usize,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        if result.is_ok() {
+            counter.set(counter.get() + 1);
+            self.bytes.set(self.bytes.get() - old_size + new_size);
+        }
+        result
+    }
+}
+
+unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(Global.allocate(layout), &self.allocations, 0, layout.size())
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(
+            Global.allocate_zeroed(layout),
+            &self.allocations,
+            0,
+            layout.size(),
+        )
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.set(self.deallocations.get() + 1);
+        self.bytes.set(self.bytes.get() - layout.size());
+        Global.deallocate(ptr, layout)
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(
+            Global.grow(ptr, old_layout, new_layout),
+            &self.grows,
+            old_layout.size(),
+            new_layout.size(),
+        )
+    }
+
+    unsafe fn grow_zeroed(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(
+            Global.grow_zeroed(ptr, old_layout, new_layout),
+            &self.grows,
+            old_layout.size(),
+            new_layout.size(),
+        )
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.record(
+            Global.shrink(ptr, old_layout, new_layout),
+            &self.shrinks,
+            old_layout.size(),
+            new_layout.size(),
+        )
+    }
+}
+
+/// Allocator forwarding to `Global`, which records the layouts of the last allocation and
+/// deallocation.
+#[derive(Default)]
+pub struct Recording {
+    pub allocated: Cell<Option<Layout>>,
+    pub deallocated: Cell<Option<Layout>>,
+}
+
+unsafe impl Allocator for Recording {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocated.set(Some(layout));
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocated.set(Some(layout));
+        Global.deallocate(ptr, layout)
+    }
+}
+
+/// Allocator forwarding to [`Counting`], which rounds every allocation up to a multiple of 64
+/// bytes.
+#[derive(Default)]
+pub struct Rounding {
+    pub counting: Counting,
+}
+
+impl Rounding {
+    unsafe fn round(layout: Layout) -> Layout {
+        Layout::from_size_align_unchecked((layout.size() + 63) & !63, layout.align())
+    }
+}
+
+unsafe impl Allocator for Rounding {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if layout.size() > isize::MAX as usize - 63 {
+            return Err(AllocError);
+        }
+        self.counting.allocate(unsafe { Self::round(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.counting.deallocate(ptr, Self::round(layout))
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        self.counting
+            .shrink(ptr, Self::round(old_layout), Self::round(new_layout))
+    }
+}
@@ -0,0 +1,317 @@
+//! A growable vector on top of any growable buffer.
+
+use crate::{
+    boxed::{handle_reserve, Box},
+    buffer::{
+        AllocateBuffer,
+        AllocatedBuffer,
+        Buffer,
+        GrowableBuffer,
+        ReadBuffer,
+        TakeInitialized,
+    },
+};
+use alloc::collections::TryReserveError;
+use core::{
+    alloc::AllocError,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr,
+    slice,
+};
+
+/// A contiguous growable vector, which is generic over the buffer storing its elements.
+///
+/// The elements are stored in a boxed slice of uninitialized elements and the vector tracks how
+/// many of them are initialized. The buffer decides how the vector grows: an [`AllocatedBuffer`]
+/// reallocates on the heap, an [`InlineBuffer`] fails when it's full, and a [`SmallBuffer`]
+/// moves its elements from inline storage to the heap.
+///
+/// [`InlineBuffer`]: crate::buffer::InlineBuffer
+/// [`SmallBuffer`]: crate::buffer::SmallBuffer
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "global")] {
+/// use storages::vec::BufferVec;
+///
+/// let mut values = BufferVec::new();
+/// values.push(1);
+/// values.push(2);
+///
+/// assert_eq!(values.pop(), Some(2));
+/// assert_eq!(*values, [1]);
+/// # }
+/// ```
+///
+/// Storing up to four elements inline before moving them to the heap:
+///
+/// ```
+/// #![feature(allocator_api)]
+///
+/// use std::alloc::Global;
+/// use storages::{buffer::SmallBuffer, vec::BufferVec};
+///
+/// let mut values = BufferVec::<u32, SmallBuffer<u32, Global, 4>>::with_capacity_in(0, Global)?;
+/// values.extend(0..4);
+/// assert_eq!(values.capacity(), 4);
+///
+/// values.push(4);
+/// assert!(values.capacity() > 4);
+/// assert_eq!(*values, [0, 1, 2, 3, 4]);
+/// # Ok::<(), core::alloc::AllocError>(())
+/// ```
+pub struct BufferVec<
+    T,
+    B = AllocatedBuffer<[T]>,
+    D = <B as ReadBuffer<[mem::MaybeUninit<T>]>>::ExternalData,
+> where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    slots: Box<[mem::MaybeUninit<T>], B, D>,
+    len: usize,
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> BufferVec<T> {
+    /// Creates an empty vector on the global heap, which doesn't allocate until elements are
+    /// pushed.
+    pub fn new() -> Self {
+        Self::from_box(Box::<[T]>::new_uninit_slice(0))
+    }
+}
+
+#[cfg(feature = "global")]
+#[cfg_attr(doc, doc(cfg(feature = "global")))]
+impl<T> Default for BufferVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, B, D> BufferVec<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    /// Creates an empty vector, which can hold at least `capacity` elements.
+    ///
+    /// Returns an error if the buffer can't provide the requested capacity.
+    pub fn with_capacity_in(capacity: usize, data: D) -> Result<Self, AllocError>
+    where
+        B: AllocateBuffer<[mem::MaybeUninit<T>]>,
+    {
+        let buffer = B::allocate(&data, capacity)?;
+        Ok(Self::new_in(buffer, data))
+    }
+
+    /// Creates a vector, which uses all elements of `buffer` as capacity.
+    ///
+    /// Elements the buffer already contains, like those of a non-empty [`InlineBuffer`], become
+    /// the first elements of the vector.
+    ///
+    /// [`InlineBuffer`]: crate::buffer::InlineBuffer
+    pub fn new_in(mut buffer: B, data: D) -> Self {
+        let len = buffer.take_initialized();
+        let mut values = Self::from_box(unsafe { Box::from_buffer(buffer, data) });
+        values.len = len;
+        values
+    }
+
+    fn from_box(slots: Box<[mem::MaybeUninit<T>], B, D>) -> Self {
+        Self { slots, len: 0 }
+    }
+
+    /// Returns the number of elements the vector can hold without growing.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes the last element and returns it, or `None` if the vector is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.slots[self.len].as_ptr().read() })
+    }
+}
+
+impl<T, B, D> BufferVec<T, B, D>
+where
+    B: GrowableBuffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    /// Ensures that the vector can hold at least `additional` more elements.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let available = self.capacity() - self.len;
+        if additional <= available {
+            return Ok(());
+        }
+        self.slots.try_reserve(additional - available)
+    }
+
+    /// Appends an element to the back of the vector, growing the buffer if needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer can't grow, e.g. if an [`InlineBuffer`] is full.
+    ///
+    /// [`InlineBuffer`]: crate::buffer::InlineBuffer
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity() {
+            handle_reserve(self.try_reserve(1));
+        }
+        self.slots[self.len] = mem::MaybeUninit::new(value);
+        self.len += 1;
+    }
+}
+
+impl<T, B, D> Extend<T> for BufferVec<T, B, D>
+where
+    B: GrowableBuffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, B, D> Deref for BufferVec<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(<[_]>::as_ptr(&self.slots).cast(), self.len) }
+    }
+}
+
+impl<T, B, D> DerefMut for BufferVec<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    fn deref_mut(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(<[_]>::as_mut_ptr(&mut self.slots).cast(), self.len) }
+    }
+}
+
+impl<T, B, D> Drop for BufferVec<T, B, D>
+where
+    B: Buffer<[mem::MaybeUninit<T>], ExternalData = D>,
+{
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place::<[T]>(&mut **self) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::{InlineBuffer, SmallBuffer},
+        testing::Counting,
+    };
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    #[test]
+    fn allocated() {
+        let counting = Counting::default();
+        let value = Rc::new(());
+        let mut values =
+            BufferVec::<_, AllocatedBuffer<[Rc<()>], _>>::with_capacity_in(2, &counting).unwrap();
+        for _ in 0..10 {
+            values.push(Rc::clone(&value));
+        }
+        assert_eq!(values.len(), 10);
+        assert!(values.capacity() >= 10);
+        assert_eq!(Rc::strong_count(&value), 11);
+
+        drop(values.pop());
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+        assert_eq!(counting.allocations.get(), 1);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+
+    #[test]
+    fn inline() {
+        let value = Rc::new(());
+        let mut values = BufferVec::<_, InlineBuffer<Rc<()>, 3>>::with_capacity_in(3, ()).unwrap();
+        values.extend((0..3).map(|_| Rc::clone(&value)));
+        assert_eq!(values.capacity(), 3);
+        assert!(values.try_reserve(1).is_err());
+
+        drop(values.pop());
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(values);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        assert!(BufferVec::<u8, InlineBuffer<u8, 3>>::with_capacity_in(4, ()).is_err());
+    }
+
+    #[test]
+    fn inline_with_elements() {
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut buffer = InlineBuffer::<Counted<'_>, 2>::new();
+        buffer.try_push(Counted(&drops)).ok().unwrap();
+
+        let mut values = BufferVec::new_in(buffer, ());
+        assert_eq!(values.len(), 1);
+        values.push(Counted(&drops));
+        assert_eq!(values.len(), 2);
+        assert_eq!(drops.get(), 0);
+
+        drop(values.pop());
+        assert_eq!(drops.get(), 1);
+        drop(values);
+        assert_eq!(drops.get(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn inline_overflow() {
+        let mut values = BufferVec::<u8, InlineBuffer<u8, 1>>::with_capacity_in(0, ()).unwrap();
+        values.push(1);
+        values.push(2);
+    }
+
+    #[test]
+    fn small() {
+        let counting = Counting::default();
+        let mut values =
+            BufferVec::<u32, SmallBuffer<u32, _, 4>>::with_capacity_in(0, &counting).unwrap();
+        values.extend(0..4);
+        assert_eq!(counting.allocations.get(), 0);
+
+        values.extend(4..100);
+        assert_eq!(counting.allocations.get(), 1);
+        assert!(values.iter().copied().eq(0..100));
+
+        values[99] = 0;
+        assert_eq!(values.pop(), Some(0));
+        drop(values);
+        assert_eq!(counting.deallocations.get(), 1);
+    }
+}
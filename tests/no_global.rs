@@ -0,0 +1,37 @@
+//! Checks the API available without the `global` feature, as on targets without a global
+//! allocator.
+//!
+//! Run with `cargo test --no-default-features --test no_global`.
+
+#![cfg(not(feature = "global"))]
+#![feature(allocator_api)]
+
+use std::alloc::System;
+use storages::{
+    boxed::{collect_in, Box, HeapBox},
+    buffer::ReadBuffer,
+};
+
+/// Provides `Box::new` as fallback, which is only picked if `Box` has no inherent `new`.
+trait MissingNew {
+    #[allow(clippy::new_ret_no_self)]
+    fn new(_value: u32) -> &'static str {
+        "missing"
+    }
+}
+
+impl<T: ?Sized, B: ReadBuffer<T, ExternalData = D>, D> MissingNew for Box<T, B, D> {}
+
+#[test]
+fn global_constructors_are_absent() {
+    assert_eq!(Box::<u32>::new(5), "missing");
+}
+
+#[test]
+fn allocator_constructors() {
+    let five = HeapBox::try_new_in(5, System).ok().unwrap();
+    assert_eq!(*five, 5);
+
+    let values = collect_in(1..4, System);
+    assert_eq!(*values, [1, 2, 3]);
+}